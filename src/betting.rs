@@ -0,0 +1,646 @@
+use crate::game::{Player, PlayerStatus};
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum Action {
+    Fold,
+    Check,
+    Call,
+    // Raise the street's bet to the given total.
+    Raise(u32),
+    AllIn
+}
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum ActionError {
+    // Action on this street is already closed.
+    RoundComplete,
+    // Checking is not allowed while facing a bet.
+    IllegalCheck,
+    // A raise must be at least to the given total.
+    RaiseTooSmall { minimum: u32 },
+    // The player does not have enough chips for the raise.
+    NotEnoughChips,
+    // The limit structure only allows a raise up to the given total.
+    RaiseTooLarge { maximum: u32 },
+    // A fixed-limit street allows no more raises.
+    RaiseCapped,
+    // The player has acted and only faces a short all-in since, so they may
+    // call or fold but not raise.
+    BettingNotReopened
+}
+
+impl std::fmt::Display for ActionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use ActionError::*;
+
+        match self {
+            RoundComplete             => write!(f, "the betting round is complete"),
+            IllegalCheck              => write!(f, "cannot check facing a bet"),
+            RaiseTooSmall { minimum } => write!(f, "a raise must be to at least {}", minimum),
+            NotEnoughChips            => write!(f, "not enough chips"),
+            RaiseTooLarge { maximum } => write!(f, "a raise may be to at most {}", maximum),
+            RaiseCapped               => write!(f, "the betting is capped"),
+            BettingNotReopened        => write!(f, "a short all-in does not reopen the betting")
+        }
+    }
+}
+
+impl std::error::Error for ActionError {}
+
+// The smallest total a no-limit raise may be to: the last raise must at least
+// be matched.
+pub fn min_raise(current_bet: u32, last_raise_size: u32) -> u32 {
+    current_bet + last_raise_size
+}
+
+// The largest total a pot-limit raise may be to: calling first and then
+// raising by the size of the pot. 'pot' includes all bets made so far, such
+// as the 'current_bet' being faced.
+pub fn max_pot_limit_raise(pot: u32, to_call: u32, current_bet: u32) -> u32 {
+    current_bet + pot + to_call
+}
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum LimitStructure {
+    // A raise may be up to the whole stack.
+    NoLimit,
+    // A raise may be up to the size of the pot after calling.
+    PotLimit,
+    // Every bet and raise is exactly 'bet_size', and only 'cap' of them are
+    // allowed on a street.
+    FixedLimit { bet_size: u32, cap: u32 }
+}
+
+// A player's chips and state as seen by a single betting round.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct Seat {
+    // Chips behind, not yet put in on this street.
+    pub stack: u32,
+    // Chips put in on this street.
+    pub bet: u32,
+    // Chips put in as an ante or a dead blind, which count towards the pot
+    // but not the bet.
+    pub ante: u32,
+    pub status: PlayerStatus,
+    acted: bool
+}
+
+// The betting on a single street.
+pub struct BettingRound {
+    seats: Vec<Seat>,
+    // The total every player has to put in on this street to stay in.
+    current_bet: u32,
+    // The smallest increment a raise must add to 'current_bet'.
+    min_raise: u32,
+    big_blind: u32,
+    limit: LimitStructure,
+    // Bets and raises made on this street. A posted big blind is the first.
+    raises: u32,
+    // Chips put in on earlier streets.
+    earlier_pot: u32,
+    turn: usize
+}
+
+impl BettingRound {
+    // 'stacks' are given in seat order and 'big_blind' is the smallest bet.
+    // The betting is no-limit.
+    pub fn new(stacks: &[u32], first_to_act: usize, big_blind: u32) -> BettingRound {
+        BettingRound::with_limit(stacks, first_to_act, big_blind, LimitStructure::NoLimit)
+    }
+
+    pub fn with_limit(
+        stacks: &[u32],
+        first_to_act: usize,
+        big_blind: u32,
+        limit: LimitStructure
+    ) -> BettingRound
+    {
+        let seats = stacks.iter()
+            .map(|&stack| Seat {
+                stack,
+                bet: 0,
+                ante: 0,
+                status: if stack > 0 { PlayerStatus::Active } else { PlayerStatus::AllIn },
+                acted: false
+            })
+            .collect();
+
+        let mut round = BettingRound {
+            seats,
+            current_bet: 0,
+            min_raise: big_blind,
+            big_blind,
+            limit,
+            raises: 0,
+            earlier_pot: 0,
+            turn: first_to_act
+        };
+
+        if !round.can_act(first_to_act) {
+            round.advance_turn();
+        }
+
+        round
+    }
+
+    pub fn seats(&self) -> &[Seat] {
+        &self.seats
+    }
+
+    pub fn current_bet(&self) -> u32 {
+        self.current_bet
+    }
+
+    pub fn min_raise(&self) -> u32 {
+        self.min_raise
+    }
+
+    pub fn limit(&self) -> LimitStructure {
+        self.limit
+    }
+
+    // The seat whose turn it is to act.
+    pub fn turn(&self) -> usize {
+        self.turn
+    }
+
+    // All chips put in on this street, antes included.
+    pub fn pot(&self) -> u32 {
+        self.seats.iter().map(|seat| seat.ante + seat.bet).sum()
+    }
+
+    // Takes an ante from every active player, or their whole stack if it is
+    // smaller. 'players' must be given in seat order; their money is reduced
+    // along with the stacks of their seats.
+    pub fn post_antes(&mut self, players: &mut [Player], ante: u32) {
+        assert_eq!(players.len(), self.seats.len(), "every seat needs a player");
+
+        for (player, seat) in players.iter_mut().zip(&mut self.seats) {
+            if seat.status != PlayerStatus::Active {
+                continue;
+            }
+
+            let paid = match player.bet(ante.min(seat.stack)) {
+                Ok(paid) => paid,
+                Err(_) => continue
+            };
+
+            seat.stack -= paid;
+            seat.ante += paid;
+
+            if seat.stack == 0 {
+                seat.status = PlayerStatus::AllIn;
+            }
+        }
+
+        if !self.can_act(self.turn) {
+            self.advance_turn();
+        }
+    }
+
+    // Posts a blind of 'amount', or the whole stack if it is smaller, for
+    // 'seat'. Unlike a bet, a blind leaves the player the option to raise
+    // when the action gets back to them.
+    pub fn post_blind(&mut self, seat: usize, amount: u32) {
+        let turn = self.turn;
+
+        self.turn = seat;
+        self.commit(amount.min(self.seats[seat].stack));
+        self.turn = turn;
+
+        // The big blind opens the betting, so it counts towards the cap.
+        if self.seats[seat].bet >= self.big_blind && self.raises == 0 {
+            self.raises = 1;
+        }
+
+        if !self.can_act(self.turn) {
+            self.advance_turn();
+        }
+    }
+
+    // Puts a dead blind of 'amount', or the whole stack if it is smaller,
+    // for 'seat' straight into the pot. It does not count towards the seat's
+    // bet, so calling still costs the full amount.
+    pub fn post_dead_blind(&mut self, seat: usize, amount: u32) {
+        let seat = &mut self.seats[seat];
+        let paid = amount.min(seat.stack);
+
+        seat.stack -= paid;
+        seat.ante += paid;
+
+        if seat.stack == 0 {
+            seat.status = PlayerStatus::AllIn;
+        }
+
+        if !self.can_act(self.turn) {
+            self.advance_turn();
+        }
+    }
+
+    // The betting round of the next street: the stacks and whoever folded or
+    // went all-in carry over, while the bets start again from nothing.
+    pub fn next_street(&self, first_to_act: usize) -> BettingRound {
+        let seats = self.seats.iter()
+            .map(|seat| Seat { stack: seat.stack, bet: 0, ante: 0, status: seat.status, acted: false })
+            .collect();
+
+        let mut round = BettingRound {
+            seats,
+            current_bet: 0,
+            min_raise: self.big_blind,
+            big_blind: self.big_blind,
+            limit: self.limit,
+            raises: 0,
+            earlier_pot: self.earlier_pot + self.pot(),
+            turn: first_to_act
+        };
+
+        if !round.can_act(first_to_act) {
+            round.advance_turn();
+        }
+
+        round
+    }
+
+    // Opens the betting with a forced bet of 'amount' from 'seat', as made by
+    // the lowest upcard in stud. The player to their left acts next.
+    pub fn bring_in(&mut self, seat: usize, amount: u32) {
+        self.turn = seat;
+        self.commit(amount.min(self.seats[seat].stack));
+        self.seats[seat].acted = true;
+        self.advance_turn();
+    }
+
+    // Applies 'action' on behalf of the player whose turn it is.
+    pub fn apply(&mut self, action: Action) -> Result<(), ActionError> {
+        if self.is_complete() {
+            return Err(ActionError::RoundComplete);
+        }
+
+        let seat = self.seats[self.turn];
+        let current_bet = self.current_bet;
+        let to_call = current_bet - seat.bet;
+
+        let raising = match action {
+            Action::Raise(_) => true,
+            Action::AllIn    => seat.bet + seat.stack > current_bet,
+            _                => false
+        };
+
+        if raising && !self.is_reopened(self.turn) {
+            return Err(ActionError::BettingNotReopened);
+        }
+
+        match action {
+            Action::Fold => {
+                self.seats[self.turn].status = PlayerStatus::Folded;
+            }
+
+            Action::Check if to_call > 0 => {
+                return Err(ActionError::IllegalCheck);
+            }
+
+            Action::Check => {}
+
+            Action::Call => {
+                self.commit(to_call.min(seat.stack));
+            }
+
+            Action::Raise(total) => {
+                let minimum = match self.limit {
+                    LimitStructure::FixedLimit { bet_size, .. } => self.current_bet + bet_size,
+                    _ => min_raise(self.current_bet, self.min_raise)
+                };
+
+                if total < minimum {
+                    return Err(ActionError::RaiseTooSmall { minimum });
+                }
+
+                self.check_raise_limit(total, to_call)?;
+
+                if total - seat.bet > seat.stack {
+                    return Err(ActionError::NotEnoughChips);
+                }
+
+                self.commit(total - seat.bet);
+            }
+
+            Action::AllIn => {
+                // Going all-in for no more than a call is never a raise.
+                if seat.bet + seat.stack > self.current_bet {
+                    self.check_raise_limit(seat.bet + seat.stack, to_call)?;
+                }
+
+                self.commit(seat.stack);
+            }
+        }
+
+        // Only a full raise counts towards the cap of a fixed-limit street.
+        if self.current_bet >= current_bet + self.min_raise {
+            self.raises += 1;
+        }
+
+        self.seats[self.turn].acted = true;
+
+        if !self.is_complete() {
+            self.advance_turn();
+        }
+
+        Ok(())
+    }
+
+    // Action is closed once every player who can still act has acted and
+    // matched the current bet, or when only one player is left in the hand.
+    pub fn is_complete(&self) -> bool {
+        let in_hand = self.seats.iter()
+            .filter(|seat| seat.status != PlayerStatus::Folded)
+            .count();
+
+        in_hand <= 1 || self.seats.iter()
+            .filter(|seat| seat.status == PlayerStatus::Active)
+            .all(|seat| seat.acted && seat.bet == self.current_bet)
+    }
+
+    // Moves 'amount' chips from the stack of the player to act into their
+    // bet, raising the current bet if it is exceeded.
+    fn commit(&mut self, amount: u32) {
+        let seat = &mut self.seats[self.turn];
+
+        seat.stack -= amount;
+        seat.bet += amount;
+
+        if seat.stack == 0 {
+            seat.status = PlayerStatus::AllIn;
+        }
+
+        let total = seat.bet;
+
+        if total > self.current_bet {
+            let raise = total - self.current_bet;
+
+            // A short all-in does not change the minimum raise, nor does it
+            // let the players who already acted raise again. They still have
+            // to call it, since their bets fall short.
+            if raise >= self.min_raise {
+                for (i, seat) in self.seats.iter_mut().enumerate() {
+                    if i != self.turn {
+                        seat.acted = false;
+                    }
+                }
+            }
+
+            self.min_raise = self.min_raise.max(raise);
+            self.current_bet = total;
+        }
+    }
+
+    // Whether 'seat' may raise: either it has not acted since the last full
+    // raise, or the short all-ins it faces since add up to a full raise.
+    fn is_reopened(&self, seat: usize) -> bool {
+        let seat = &self.seats[seat];
+
+        !seat.acted || self.current_bet - seat.bet >= self.min_raise
+    }
+
+    // Whether the limit structure allows a raise to 'total' by a player who
+    // has 'to_call' left to call.
+    fn check_raise_limit(&self, total: u32, to_call: u32) -> Result<(), ActionError> {
+        let maximum = match self.limit {
+            LimitStructure::NoLimit => return Ok(()),
+            LimitStructure::PotLimit => {
+                max_pot_limit_raise(self.earlier_pot + self.pot(), to_call, self.current_bet)
+            }
+            LimitStructure::FixedLimit { bet_size, cap } => {
+                if self.raises >= cap {
+                    return Err(ActionError::RaiseCapped);
+                }
+
+                self.current_bet + bet_size
+            }
+        };
+
+        if total > maximum {
+            return Err(ActionError::RaiseTooLarge { maximum });
+        }
+
+        Ok(())
+    }
+
+    fn can_act(&self, seat: usize) -> bool {
+        self.seats[seat].status == PlayerStatus::Active
+    }
+
+    fn advance_turn(&mut self) {
+        for _ in 0..self.seats.len() {
+            self.turn = (self.turn + 1) % self.seats.len();
+
+            if self.can_act(self.turn) {
+                return;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::PlayerId;
+
+    #[test]
+    fn illegal_check() {
+        let mut round = BettingRound::new(&[1000, 1000, 1000], 0, 20);
+
+        round.apply(Action::Raise(60)).unwrap();
+
+        assert_eq!(round.turn(), 1);
+        assert_eq!(round.apply(Action::Check), Err(ActionError::IllegalCheck));
+
+        round.apply(Action::Call).unwrap();
+        round.apply(Action::Fold).unwrap();
+
+        assert!(round.is_complete());
+        assert_eq!(round.apply(Action::Check), Err(ActionError::RoundComplete));
+    }
+
+    #[test]
+    fn raise_too_small() {
+        let mut round = BettingRound::new(&[1000, 1000, 1000], 0, 20);
+
+        assert_eq!(round.apply(Action::Raise(10)), Err(ActionError::RaiseTooSmall { minimum: 20 }));
+
+        round.apply(Action::Raise(50)).unwrap();
+
+        // The last raise was 50, so the next must be to at least 100.
+        assert_eq!(round.apply(Action::Raise(80)), Err(ActionError::RaiseTooSmall { minimum: 100 }));
+        assert_eq!(round.apply(Action::Raise(2000)), Err(ActionError::NotEnoughChips));
+
+        round.apply(Action::Raise(100)).unwrap();
+
+        assert_eq!(round.current_bet(), 100);
+        assert_eq!(round.min_raise(), 50);
+    }
+
+    #[test]
+    fn antes_and_bring_in() {
+        let mut players = vec![
+            Player::new(PlayerId(0), "a", 1000),
+            Player::new(PlayerId(1), "b", 3),
+            Player::new(PlayerId(2), "c", 1000)
+        ];
+
+        let stacks: Vec<u32> = players.iter().map(|player| player.money).collect();
+        let mut round = BettingRound::new(&stacks, 1, 20);
+
+        round.post_antes(&mut players, 5);
+
+        // The short stack is all-in for their ante and cannot act.
+        assert_eq!(players[1].money, 0);
+        assert_eq!(players[1].status, PlayerStatus::AllIn);
+        assert_eq!(round.seats()[1].status, PlayerStatus::AllIn);
+        assert_eq!(round.seats()[1].ante, 3);
+        assert_eq!(round.seats()[0].stack, 995);
+        assert_eq!(players[0].money, 995);
+        assert_eq!(round.pot(), 13);
+        assert_eq!(round.turn(), 2);
+
+        round.bring_in(2, 10);
+
+        assert_eq!(round.current_bet(), 10);
+        assert_eq!(round.seats()[2].stack, 985);
+        assert_eq!(round.turn(), 0);
+
+        round.apply(Action::Call).unwrap();
+
+        assert!(round.is_complete());
+        assert_eq!(round.pot(), 33);
+    }
+
+    #[test]
+    fn raise_limits() {
+        // A pot of 100 and a bet of 30: calling makes the pot 160, so the
+        // raise may be by up to 160, to 190 in total.
+        assert_eq!(max_pot_limit_raise(130, 30, 30), 190);
+
+        // Having put in 10 already, only 20 remain to call.
+        assert_eq!(max_pot_limit_raise(140, 20, 30), 190);
+
+        // Preflop with blinds of 5 and 10, the first player may raise to 35.
+        assert_eq!(max_pot_limit_raise(15, 10, 10), 35);
+
+        assert_eq!(min_raise(30, 30), 60);
+        assert_eq!(min_raise(100, 50), 150);
+    }
+
+    #[test]
+    fn fixed_limit_raises() {
+        let limit = LimitStructure::FixedLimit { bet_size: 20, cap: 4 };
+        let mut round = BettingRound::with_limit(&[1000, 1000, 1000], 0, 20, limit);
+
+        assert_eq!(round.apply(Action::Raise(60)), Err(ActionError::RaiseTooLarge { maximum: 20 }));
+        assert_eq!(round.apply(Action::AllIn), Err(ActionError::RaiseTooLarge { maximum: 20 }));
+
+        round.apply(Action::Raise(20)).unwrap();
+
+        assert_eq!(round.apply(Action::Raise(30)), Err(ActionError::RaiseTooSmall { minimum: 40 }));
+
+        round.apply(Action::Raise(40)).unwrap();
+        round.apply(Action::Raise(60)).unwrap();
+        round.apply(Action::Raise(80)).unwrap();
+
+        // The fourth bet caps the betting, which only leaves calling.
+        assert_eq!(round.apply(Action::Raise(100)), Err(ActionError::RaiseCapped));
+        assert_eq!(round.apply(Action::AllIn), Err(ActionError::RaiseCapped));
+
+        round.apply(Action::Call).unwrap();
+        round.apply(Action::Call).unwrap();
+
+        assert!(round.is_complete());
+        assert_eq!(round.pot(), 240);
+
+        // The cap starts over on the next street.
+        let mut next = round.next_street(0);
+
+        assert_eq!(next.limit(), limit);
+        next.apply(Action::Raise(20)).unwrap();
+
+        // Preflop the big blind is the first of the four bets.
+        let mut preflop = BettingRound::with_limit(&[1000, 1000, 1000], 0, 20, limit);
+
+        preflop.post_blind(1, 10);
+        preflop.post_blind(2, 20);
+
+        preflop.apply(Action::Raise(40)).unwrap();
+        preflop.apply(Action::Raise(60)).unwrap();
+        preflop.apply(Action::Raise(80)).unwrap();
+
+        assert_eq!(preflop.apply(Action::Raise(100)), Err(ActionError::RaiseCapped));
+    }
+
+    #[test]
+    fn pot_limit_raises() {
+        let mut round = BettingRound::with_limit(&[1000, 1000, 1000], 0, 20, LimitStructure::PotLimit);
+
+        round.post_blind(1, 10);
+        round.post_blind(2, 20);
+
+        assert_eq!(round.apply(Action::Raise(80)), Err(ActionError::RaiseTooLarge { maximum: 70 }));
+        round.apply(Action::Raise(70)).unwrap();
+
+        round.apply(Action::Call).unwrap();
+        round.apply(Action::Call).unwrap();
+
+        // Bets on earlier streets count towards the pot.
+        let mut flop = round.next_street(1);
+
+        assert_eq!(flop.apply(Action::Raise(220)), Err(ActionError::RaiseTooLarge { maximum: 210 }));
+        assert_eq!(flop.apply(Action::Raise(210)), Ok(()));
+    }
+
+    #[test]
+    fn short_all_in_does_not_reopen_betting() {
+        let limit = LimitStructure::FixedLimit { bet_size: 100, cap: 2 };
+        let mut round = BettingRound::with_limit(&[1000, 1000, 130], 0, 100, limit);
+
+        round.apply(Action::Raise(100)).unwrap();
+        round.apply(Action::Call).unwrap();
+
+        // Going all-in for 30 more is less than a full raise.
+        round.apply(Action::AllIn).unwrap();
+
+        assert_eq!(round.current_bet(), 130);
+        assert_eq!(round.turn(), 0);
+        assert_eq!(round.apply(Action::Raise(230)), Err(ActionError::BettingNotReopened));
+        assert_eq!(round.apply(Action::AllIn), Err(ActionError::BettingNotReopened));
+
+        round.apply(Action::Call).unwrap();
+        assert!(!round.is_complete());
+        round.apply(Action::Call).unwrap();
+
+        assert!(round.is_complete());
+        assert_eq!(round.pot(), 390);
+
+        // Nor does it count towards the cap.
+        assert_eq!(round.raises, 1);
+    }
+
+    #[test]
+    fn round_completion() {
+        let mut round = BettingRound::new(&[1000, 40, 1000], 0, 20);
+
+        round.apply(Action::Check).unwrap();
+        round.apply(Action::Check).unwrap();
+        assert!(!round.is_complete());
+        round.apply(Action::Raise(100)).unwrap();
+
+        // Only the players who checked still have to respond.
+        round.apply(Action::Call).unwrap();
+        assert!(!round.is_complete());
+        round.apply(Action::AllIn).unwrap();
+
+        assert!(round.is_complete());
+        assert_eq!(round.seats()[0].bet, 100);
+        assert_eq!(round.seats()[1].bet, 40);
+        assert_eq!(round.seats()[1].status, PlayerStatus::AllIn);
+        assert_eq!(round.seats()[2].bet, 100);
+    }
+}
@@ -0,0 +1,684 @@
+use itertools::Itertools;
+
+#[derive(Eq, PartialEq, PartialOrd, Ord, Hash, Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CardSuit {
+    Spades,
+    Hearts,
+    Clubs,
+    Diamonds
+}
+
+impl std::fmt::Display for CardSuit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use CardSuit::*;
+
+        match self {
+            Spades   => '♠',
+            Hearts   => '♥',
+            Clubs    => '♣',
+            Diamonds => '♦'
+        }.fmt(f)
+    }
+}
+
+impl CardSuit {
+    pub fn all() -> [CardSuit; 4] {
+        use CardSuit::*;
+
+        [Spades, Hearts, Clubs, Diamonds]
+    }
+
+    pub fn letter(&self) -> char {
+        use CardSuit::*;
+
+        match self {
+            Spades   => 's',
+            Hearts   => 'h',
+            Clubs    => 'c',
+            Diamonds => 'd'
+        }
+    }
+
+    pub fn from_letter(letter: char) -> Option<CardSuit> {
+        use CardSuit::*;
+
+        match letter.to_ascii_lowercase() {
+            's' => Some(Spades),
+            'h' => Some(Hearts),
+            'c' => Some(Clubs),
+            'd' => Some(Diamonds),
+            _   => None
+        }
+    }
+
+    pub fn color(&self) -> Color {
+        match self {
+            CardSuit::Hearts | CardSuit::Diamonds => Color::Red,
+            CardSuit::Spades | CardSuit::Clubs    => Color::Black
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub enum Color {
+    Red,
+    Black
+}
+
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CardRank {
+    Two,
+    Three,
+    Four,
+    Five,
+    Six,
+    Seven,
+    Eight,
+    Nine,
+    Ten,
+    Jack,
+    Queen,
+    King,
+    Ace
+}
+
+impl std::fmt::Display for CardRank {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use CardRank::*;
+
+        match self {
+            Two   => "2",
+            Three => "3",
+            Four  => "4",
+            Five  => "5",
+            Six   => "6",
+            Seven => "7",
+            Eight => "8",
+            Nine  => "9",
+            Ten   => "10",
+            Jack  => "J",
+            Queen => "Q",
+            King  => "K",
+            Ace   => "A"
+        }.fmt(f)
+    }
+}
+
+impl CardRank {
+    // Every rank from Two up to Ace.
+    pub fn all() -> [CardRank; 13] {
+        use CardRank::*;
+
+        [Two, Three, Four, Five, Six, Seven, Eight, Nine, Ten, Jack, Queen, King, Ace]
+    }
+
+    // The next higher rank, none for an Ace.
+    pub fn successor(&self) -> Option<CardRank> {
+        Self::all().get(*self as usize + 1).copied()
+    }
+
+    // Numeric rank from 2 to 14, ace high.
+    pub fn value(&self) -> u8 {
+        *self as u8 + 2
+    }
+
+    // Single-character code of the rank, 'T' for Ten.
+    pub fn code(&self) -> char {
+        match self {
+            CardRank::Ten => 'T',
+            _ => self.to_string().chars().next().unwrap()
+        }
+    }
+
+    // English name of the rank, e.g. "Queen".
+    pub fn name(&self) -> &'static str {
+        use CardRank::*;
+
+        match self {
+            Two   => "Two",
+            Three => "Three",
+            Four  => "Four",
+            Five  => "Five",
+            Six   => "Six",
+            Seven => "Seven",
+            Eight => "Eight",
+            Nine  => "Nine",
+            Ten   => "Ten",
+            Jack  => "Jack",
+            Queen => "Queen",
+            King  => "King",
+            Ace   => "Ace"
+        }
+    }
+
+    // Plural English name of the rank, e.g. "Sixes".
+    pub fn plural_name(&self) -> &'static str {
+        use CardRank::*;
+
+        match self {
+            Two   => "Twos",
+            Three => "Threes",
+            Four  => "Fours",
+            Five  => "Fives",
+            Six   => "Sixes",
+            Seven => "Sevens",
+            Eight => "Eights",
+            Nine  => "Nines",
+            Ten   => "Tens",
+            Jack  => "Jacks",
+            Queen => "Queens",
+            King  => "Kings",
+            Ace   => "Aces"
+        }
+    }
+
+    // Numeric rank from 1 to 13, ace low.
+    pub fn value_ace_low(&self) -> u8 {
+        match self {
+            CardRank::Ace => 1,
+            _ => self.value()
+        }
+    }
+}
+
+impl std::str::FromStr for CardRank {
+    type Err = ParseCardError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        use CardRank::*;
+
+        match s {
+            "2"         => Ok(Two),
+            "3"         => Ok(Three),
+            "4"         => Ok(Four),
+            "5"         => Ok(Five),
+            "6"         => Ok(Six),
+            "7"         => Ok(Seven),
+            "8"         => Ok(Eight),
+            "9"         => Ok(Nine),
+            "T" | "10"  => Ok(Ten),
+            "J"         => Ok(Jack),
+            "Q"         => Ok(Queen),
+            "K"         => Ok(King),
+            "A"         => Ok(Ace),
+            _           => Err(ParseCardError::BadRank)
+        }
+    }
+}
+
+// Two cards are equal only if both rank and suit match. Hand comparisons
+// only look at ranks, see 'Card::same_rank'.
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub struct Card {
+    pub suit: CardSuit,
+    pub rank: CardRank
+}
+
+impl Card {
+    pub fn new(suit: CardSuit, rank: CardRank) -> Card {
+        Card { suit, rank }
+    }
+
+    // Compact code of the card such as "Ah" or "Td", the inverse of parsing.
+    pub fn code(&self) -> String {
+        format!("{}{}", self.rank.code(), self.suit.letter())
+    }
+
+    // Whether this is the exact same card, i.e. both rank and suit match.
+    pub fn same_card(&self, other: &Card) -> bool {
+        self.rank == other.rank && self.suit == other.suit
+    }
+
+    pub fn same_rank(&self, other: &Card) -> bool {
+        self.rank == other.rank
+    }
+
+    pub fn is_red(&self) -> bool {
+        self.suit.color() == Color::Red
+    }
+
+    pub fn is_black(&self) -> bool {
+        self.suit.color() == Color::Black
+    }
+
+    // Packs the card into a number in 0..52, four consecutive numbers per rank.
+    pub fn to_index(&self) -> u8 {
+        self.rank as u8 * 4 + self.suit as u8
+    }
+
+    pub fn from_index(index: u8) -> Option<Card> {
+        let rank = *CardRank::all().get(index as usize / 4)?;
+        let suit = CardSuit::all()[index as usize % 4];
+
+        Some(Card { suit, rank })
+    }
+}
+
+impl std::fmt::Display for Card {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[{} {: >2}]", self.suit, self.rank)
+    }
+}
+
+// Cards order by rank. The suit only breaks ties so that the ordering agrees
+// with equality.
+impl Ord for Card {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.rank.cmp(&other.rank)
+            .then(self.suit.cmp(&other.suit))
+    }
+}
+
+impl PartialOrd for Card {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl From<(CardSuit, CardRank)> for Card {
+    fn from((suit, rank): (CardSuit, CardRank)) -> Card {
+        Card { suit, rank }
+    }
+}
+
+// Cards convert to and from the numbers of 'to_index'.
+impl From<Card> for u8 {
+    fn from(card: Card) -> u8 {
+        card.to_index()
+    }
+}
+
+impl TryFrom<u8> for Card {
+    type Error = CardIndexError;
+
+    fn try_from(index: u8) -> Result<Card, CardIndexError> {
+        Card::from_index(index).ok_or(CardIndexError(index))
+    }
+}
+
+// A card index must be in 0..52.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct CardIndexError(pub u8);
+
+impl std::fmt::Display for CardIndexError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} is not a card index", self.0)
+    }
+}
+
+impl std::error::Error for CardIndexError {}
+
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum ParseCardError {
+    // The card code is not two characters long (three for "10").
+    WrongLength,
+    BadRank,
+    BadSuit,
+    // A card in a list of cards failed to parse; 'index' counts from zero.
+    BadToken { index: usize, error: Box<ParseCardError> }
+}
+
+impl std::fmt::Display for ParseCardError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use ParseCardError::*;
+
+        match self {
+            WrongLength => "card code must be a rank followed by a suit, e.g. \"Ah\"".fmt(f),
+            BadRank     => "unrecognized card rank".fmt(f),
+            BadSuit     => "unrecognized card suit".fmt(f),
+            BadToken { index, error } => write!(f, "card #{}: {}", index + 1, error)
+        }
+    }
+}
+
+impl std::error::Error for ParseCardError {}
+
+// Parses compact card codes such as "As", "Td", "10h" or "2c".
+impl std::str::FromStr for Card {
+    type Err = ParseCardError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let len = s.chars().count();
+
+        if !(2..=3).contains(&len) {
+            return Err(ParseCardError::WrongLength);
+        }
+
+        let (rank, suit) = s.split_at(s.char_indices().last().unwrap().0);
+
+        let rank: CardRank = rank.parse()?;
+
+        let suit = suit.chars().next()
+            .and_then(CardSuit::from_letter)
+            .ok_or(ParseCardError::BadSuit)?;
+
+        Ok(Card { suit, rank })
+    }
+}
+
+// Parses a list of card codes separated by whitespace and/or commas, such as
+// "Ah Kh Qh Jh Th" or "Ah,Kh".
+pub fn parse_cards(s: &str) -> Result<Vec<Card>, ParseCardError> {
+    s.split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|token| !token.is_empty())
+        .enumerate()
+        .map(|(index, token)| {
+            token.parse().map_err(|error| ParseCardError::BadToken {
+                index,
+                error: Box::new(error)
+            })
+        })
+        .collect()
+}
+
+// Builds a 'Vec<Card>' from card codes, e.g. 'cards!["Ah", "Kh", "Qh"]'.
+// An invalid code panics with the code and the reason it was rejected.
+#[macro_export]
+macro_rules! cards {
+    ($($code:expr),* $(,)?) => {{
+        let cards: Vec<$crate::card::Card> = vec![$(
+            match $code.parse::<$crate::card::Card>() {
+                Ok(card) => card,
+                Err(error) => panic!("invalid card code {:?}: {}", $code, error)
+            }
+        ),*];
+
+        cards
+    }};
+}
+
+// The cards from the highest rank to the lowest. Cards of the same rank stay
+// in the order they were given.
+pub fn sorted_desc(cards: &[Card]) -> Vec<Card> {
+    let mut cards = cards.to_vec();
+    cards.sort_by_key(|card| std::cmp::Reverse(card.rank));
+    cards
+}
+
+// A key shared by every set of cards that only differs from 'cards' by a
+// relabelling of the suits, e.g. all four suited AK combinations. The order
+// of the cards does not matter. At most ten cards fit in a key.
+pub fn canonical_key(cards: &[Card]) -> u64 {
+    assert!(cards.len() <= 10, "at most ten cards have a canonical key");
+
+    // Every relabelling is tried and the smallest key wins.
+    (0..4u8).permutations(4)
+        .map(|suits| {
+            cards.iter()
+                .map(|card| card.rank as u8 * 4 + suits[card.suit as usize])
+                .sorted_unstable()
+                .fold(0, |key, index| key << 6 | (index as u64 + 1))
+        })
+        .min()
+        .unwrap()
+}
+
+// Draws the cards side by side as boxes, e.g.
+//
+//     +-----+ +-----+
+//     |A    | |10   |
+//     |  ♠  | |  ♥  |
+//     |    A| |   10|
+//     +-----+ +-----+
+pub fn render_cards(cards: &[Card]) -> String {
+    let mut lines = vec![String::new(); 5];
+
+    for (i, card) in cards.iter().enumerate() {
+        // Left-aligned in the top corner and right-aligned in the bottom one,
+        // both padded to the width of "10".
+        let rank = card.rank.to_string();
+
+        let parts = [
+            "+-----+".to_string(),
+            format!("|{:<2}   |", rank),
+            format!("|  {}  |", card.suit),
+            format!("|   {:>2}|", rank),
+            "+-----+".to_string()
+        ];
+
+        for (line, part) in lines.iter_mut().zip(parts) {
+            if i > 0 {
+                line.push(' ');
+            }
+
+            line.push_str(&part);
+        }
+    }
+
+    lines.join("\n")
+}
+
+// Cards are serialized as their compact code, e.g. "Ah".
+#[cfg(feature = "serde")]
+impl serde::Serialize for Card {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.code())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Card {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let code = String::deserialize(deserializer)?;
+
+        code.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use CardRank::*;
+    use CardSuit::*;
+
+    #[test]
+    fn card_parsing() {
+        assert_eq!("Ah".parse::<Card>().map(|c| (c.suit, c.rank)), Ok((Hearts, Ace)));
+        assert_eq!("AH".parse::<Card>().map(|c| (c.suit, c.rank)), Ok((Hearts, Ace)));
+        assert_eq!("Td".parse::<Card>().map(|c| (c.suit, c.rank)), Ok((Diamonds, Ten)));
+        assert_eq!("10d".parse::<Card>().map(|c| (c.suit, c.rank)), Ok((Diamonds, Ten)));
+        assert_eq!("2c".parse::<Card>().map(|c| (c.suit, c.rank)), Ok((Clubs, Two)));
+
+        assert_eq!("".parse::<Card>(), Err(ParseCardError::WrongLength));
+        assert_eq!("A".parse::<Card>(), Err(ParseCardError::WrongLength));
+        assert_eq!("10hh".parse::<Card>(), Err(ParseCardError::WrongLength));
+        assert_eq!("1h".parse::<Card>(), Err(ParseCardError::BadRank));
+        assert_eq!("11h".parse::<Card>(), Err(ParseCardError::BadRank));
+        assert_eq!("Ax".parse::<Card>(), Err(ParseCardError::BadSuit));
+    }
+
+    #[test]
+    fn card_parsing_round_trip() {
+        for (suit, rank) in CardSuit::all().into_iter().cartesian_product(CardRank::all()) {
+            let card = Card { suit, rank };
+
+            let parsed: Card = format!("{}{}", card.rank, card.suit.letter()).parse().unwrap();
+
+            assert_eq!(parsed.to_string(), card.to_string());
+            assert_eq!(card.code().parse(), Ok(card));
+        }
+    }
+
+    #[test]
+    fn rank_parsing_and_values() {
+        assert_eq!("2".parse::<CardRank>(), Ok(Two));
+        assert_eq!("9".parse::<CardRank>(), Ok(Nine));
+        assert_eq!("T".parse::<CardRank>(), Ok(Ten));
+        assert_eq!("10".parse::<CardRank>(), Ok(Ten));
+        assert_eq!("A".parse::<CardRank>(), Ok(Ace));
+        assert_eq!("1".parse::<CardRank>(), Err(ParseCardError::BadRank));
+        assert_eq!("0".parse::<CardRank>(), Err(ParseCardError::BadRank));
+
+        assert_eq!(Two.value(), 2);
+        assert_eq!(Ten.value(), 10);
+        assert_eq!(Ace.value(), 14);
+        assert_eq!(Ace.value_ace_low(), 1);
+        assert_eq!(King.value_ace_low(), 13);
+    }
+
+    #[test]
+    fn card_index_round_trip() {
+        for index in 0..52 {
+            let card = Card::from_index(index).unwrap();
+
+            assert_eq!(card.to_index(), index);
+        }
+
+        assert_eq!(Card { suit: Spades, rank: Two }.to_index(), 0);
+        assert_eq!(Card { suit: Diamonds, rank: Ace }.to_index(), 51);
+        assert!(Card::from_index(52).is_none());
+        assert!(Card::from_index(u8::MAX).is_none());
+    }
+
+    #[test]
+    fn card_conversions() {
+        assert_eq!(Card::from((Hearts, Queen)), Card { suit: Hearts, rank: Queen });
+
+        for index in 0..52u8 {
+            let card = Card::try_from(index).unwrap();
+
+            assert_eq!(u8::from(card), index);
+            assert_eq!(Card::from((card.suit, card.rank)), card);
+        }
+
+        let compact: Vec<u8> = cards!["Ah", "2s"].into_iter().map(u8::from).collect();
+
+        assert_eq!(compact, [49, 0]);
+        assert_eq!(Card::try_from(52), Err(CardIndexError(52)));
+        assert_eq!(CardIndexError(52).to_string(), "52 is not a card index");
+    }
+
+    #[test]
+    fn card_identity() {
+        let cards: std::collections::HashSet<Card> = (0..52)
+            .map(|index| Card::from_index(index).unwrap())
+            .collect();
+
+        assert_eq!(cards.len(), 52);
+
+        let ace_of_hearts = Card { suit: Hearts, rank: Ace };
+        let ace_of_spades = Card { suit: Spades, rank: Ace };
+
+        assert!(ace_of_hearts.same_card(&ace_of_hearts));
+        assert!(!ace_of_hearts.same_card(&ace_of_spades));
+        assert!(ace_of_hearts.same_rank(&ace_of_spades));
+        assert_ne!(ace_of_hearts, ace_of_spades);
+    }
+
+    #[test]
+    fn card_list_parsing() {
+        let cards = parse_cards("Ah Kh, Qh,Jh\tTh").unwrap();
+
+        assert_eq!(cards, vec![
+            Card { suit: Hearts, rank: Ace },
+            Card { suit: Hearts, rank: King },
+            Card { suit: Hearts, rank: Queen },
+            Card { suit: Hearts, rank: Jack },
+            Card { suit: Hearts, rank: Ten }
+        ]);
+
+        assert_eq!(parse_cards(""), Ok(vec![]));
+
+        assert_eq!(
+            parse_cards("Ah Kx Qh"),
+            Err(ParseCardError::BadToken { index: 1, error: Box::new(ParseCardError::BadSuit) })
+        );
+    }
+
+    #[test]
+    fn sorting_cards() {
+        let cards = parse_cards("5h Ac 2d Ks 5c Qd 2s").unwrap();
+        let sorted = sorted_desc(&cards);
+
+        assert_eq!(sorted, parse_cards("Ac Ks Qd 5h 5c 2d 2s").unwrap());
+        assert_eq!(sorted.first().unwrap().rank, CardRank::Ace);
+        assert_eq!(sorted.last().unwrap().rank, CardRank::Two);
+    }
+
+    #[test]
+    fn suit_colors() {
+        assert_eq!(CardSuit::Hearts.color(), Color::Red);
+        assert_eq!(CardSuit::Diamonds.color(), Color::Red);
+        assert_eq!(CardSuit::Spades.color(), Color::Black);
+        assert_eq!(CardSuit::Clubs.color(), Color::Black);
+
+        let red: Vec<Card> = parse_cards("Ah 7d").unwrap();
+        let black: Vec<Card> = parse_cards("Ks 2c").unwrap();
+
+        assert!(red.iter().all(|card| card.is_red() && !card.is_black()));
+        assert!(black.iter().all(|card| card.is_black() && !card.is_red()));
+    }
+
+    #[test]
+    fn suit_isomorphic_keys() {
+        let key = |codes: &str| canonical_key(&parse_cards(codes).unwrap());
+
+        let suited = key("Ah Kh");
+
+        assert_eq!(key("As Ks"), suited);
+        assert_eq!(key("Kd Ad"), suited);
+        assert_eq!(key("Ac Kc"), suited);
+        assert_ne!(key("Ah Ks"), suited);
+        assert_eq!(key("Ah Ks"), key("Kc Ad"));
+
+        // Pairs and boards are relabelled as a whole.
+        assert_eq!(key("Ah As"), key("Ad Ac"));
+        assert_eq!(key("Ah Kh Qh Js 2s"), key("Qc Ac Kc 2d Jd"));
+        assert_ne!(key("Ah Kh Qh Js 2s"), key("Ah Kh Qs Js 2s"));
+        assert_ne!(key("Ah"), key("Ah Ah"));
+    }
+
+    #[test]
+    fn cards_macro() {
+        let board: Vec<Card> = cards!["Ah", "Kh", "10d"];
+
+        assert_eq!(board, [
+            Card { suit: Hearts, rank: Ace },
+            Card { suit: Hearts, rank: King },
+            Card { suit: Diamonds, rank: Ten }
+        ]);
+
+        assert_eq!(cards!["2c", "Js",], parse_cards("2c Js").unwrap());
+        assert_eq!(cards![], Vec::<Card>::new());
+
+        let invalid = std::panic::catch_unwind(|| cards!["Ah", "Xh"]).unwrap_err();
+
+        assert_eq!(
+            invalid.downcast_ref::<String>().map(String::as_str),
+            Some("invalid card code \"Xh\": unrecognized card rank")
+        );
+    }
+
+    #[test]
+    fn card_rendering() {
+        let rendered = render_cards(&parse_cards("As Th").unwrap());
+
+        assert_eq!(rendered, "\
++-----+ +-----+
+|A    | |10   |
+|  ♠  | |  ♥  |
+|    A| |   10|
++-----+ +-----+");
+
+        assert_eq!(rendered.lines().count(), 5);
+        assert!(rendered.lines().all(|line| line.chars().count() == 15));
+    }
+
+    #[test]
+    fn all_ranks_and_suits() {
+        let ranks = CardRank::all();
+
+        assert_eq!(ranks.len(), 13);
+        assert!(ranks.windows(2).all(|pair| pair[0] < pair[1]));
+        assert_eq!(ranks[0], Two);
+        assert_eq!(ranks[12], Ace);
+
+        assert_eq!(CardSuit::all().len(), 4);
+
+        assert_eq!(Two.successor(), Some(Three));
+        assert_eq!(King.successor(), Some(Ace));
+        assert_eq!(Ace.successor(), None);
+    }
+}
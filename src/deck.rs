@@ -0,0 +1,391 @@
+use crate::card::{Card, CardRank, CardSuit};
+
+pub struct Deck {
+    cards: Vec<Card>
+}
+
+impl Deck {
+    // A full deck of 52 cards in suit and rank order, not shuffled. Use
+    // 'Deck::empty' for a deck without any cards.
+    pub fn new() -> Deck {
+        let mut deck = Deck::empty();
+        deck.reset();
+        deck
+    }
+
+    pub fn empty() -> Deck {
+        Deck { cards: Vec::with_capacity(52) }
+    }
+
+    pub fn generate() -> Deck {
+        let mut deck = Deck::empty();
+        deck.regenerate();
+        deck
+    }
+
+    // A full deck shuffled by a generator seeded with 'seed'. The same seed
+    // always gives the same order.
+    pub fn shuffled_from_seed(seed: u64) -> Deck {
+        use rand::SeedableRng;
+
+        let mut deck = Deck::empty();
+        deck.reset();
+        deck.shuffle_with_rng(&mut rand::rngs::StdRng::seed_from_u64(seed));
+        deck
+    }
+
+    // A deck holding exactly 'cards'. The last card is drawn first.
+    pub fn from_cards(cards: Vec<Card>) -> Deck {
+        Deck { cards }
+    }
+
+    // A full, unshuffled deck missing the given cards.
+    pub fn without(cards: &[Card]) -> Deck {
+        let mut deck = Deck::empty();
+        deck.reset();
+        deck.cards.retain(|card| !cards.contains(card));
+        deck
+    }
+
+    pub fn regenerate(&mut self) {
+        self.reset();
+        self.shuffle();
+    }
+
+    // Fill the deck with all 52 cards in suit and rank order.
+    pub fn reset(&mut self) {
+        self.reset_with_ranks(&CardRank::all());
+    }
+
+    // Fill the deck for Short Deck, which has no Twos through Fives.
+    pub fn reset_short(&mut self) {
+        self.reset();
+        self.cards.retain(|card| card.rank >= CardRank::Six);
+    }
+
+    // Fill the deck with the cards of the given ranks in all four suits, in
+    // suit and rank order, for stripped decks.
+    pub fn reset_with_ranks(&mut self, ranks: &[CardRank]) {
+        self.cards.clear();
+
+        for suit in CardSuit::all() {
+            for &rank in ranks {
+                self.cards.push(Card { suit, rank });
+            }
+        }
+    }
+
+    pub fn shuffle(&mut self) {
+        self.shuffle_with_rng(&mut rand::thread_rng());
+    }
+
+    // Fisher–Yates shuffle. Passing a seeded RNG gives a reproducible order.
+    pub fn shuffle_with_rng<R: rand::RngCore>(&mut self, rng: &mut R) {
+        use rand::Rng;
+
+        for i in (1..self.cards.len()).rev() {
+            let j = rng.gen_range(0..=i);
+            self.cards.swap(i, j);
+        }
+    }
+
+    pub fn draw(&mut self) -> Option<Card> {
+        self.cards.pop()
+    }
+
+    // Draws 'n' cards in the same order as 'draw' would, or nothing at all if
+    // fewer than 'n' cards remain.
+    pub fn draw_n(&mut self, n: usize) -> Option<Vec<Card>> {
+        if n > self.cards.len() {
+            return None;
+        }
+
+        let mut cards = self.cards.split_off(self.cards.len() - n);
+        cards.reverse();
+        Some(cards)
+    }
+
+    // The cards left, the next one to be drawn last.
+    pub fn cards(&self) -> &[Card] {
+        &self.cards
+    }
+
+    pub fn remaining(&self) -> usize {
+        self.cards.len()
+    }
+
+    pub fn contains(&self, card: &Card) -> bool {
+        self.cards.contains(card)
+    }
+
+    // Takes the given card out of the deck. Returns whether it was there.
+    pub fn remove(&mut self, card: &Card) -> bool {
+        match self.cards.iter().position(|c| c == card) {
+            Some(i) => {
+                self.cards.remove(i);
+                true
+            }
+            None => false
+        }
+    }
+}
+
+impl Default for Deck {
+    fn default() -> Deck {
+        Deck::new()
+    }
+}
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum DrawError {
+    // There is no card at the given index.
+    IndexOutOfRange(usize),
+    // The given index is listed more than once.
+    DuplicateIndex(usize),
+    DeckExhausted
+}
+
+impl std::fmt::Display for DrawError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DrawError::IndexOutOfRange(i) => write!(f, "no card to discard at index {}", i),
+            DrawError::DuplicateIndex(i) => write!(f, "card at index {} discarded twice", i),
+            DrawError::DeckExhausted => write!(f, "not enough cards left in the deck")
+        }
+    }
+}
+
+impl std::error::Error for DrawError {}
+
+// Replaces the cards of 'hand' at 'discard_indices' with cards drawn from
+// 'deck', as in draw poker. The discarded cards are out of play. Nothing is
+// changed if an error is returned.
+pub fn draw_replace(
+    hand: &mut [Card],
+    discard_indices: &[usize],
+    deck: &mut Deck
+) -> Result<(), DrawError>
+{
+    for (i, &index) in discard_indices.iter().enumerate() {
+        if index >= hand.len() {
+            return Err(DrawError::IndexOutOfRange(index));
+        }
+
+        if discard_indices[..i].contains(&index) {
+            return Err(DrawError::DuplicateIndex(index));
+        }
+    }
+
+    let replacements = deck.draw_n(discard_indices.len())
+        .ok_or(DrawError::DeckExhausted)?;
+
+    for (&index, card) in discard_indices.iter().zip(replacements) {
+        hand[index] = card;
+    }
+
+    Ok(())
+}
+
+// Draws 'n' distinct cards from a freshly shuffled deck.
+pub fn random_cards(n: usize, rng: &mut impl rand::RngCore) -> Vec<Card> {
+    let mut deck = Deck::empty();
+    deck.reset();
+    deck.shuffle_with_rng(rng);
+
+    deck.draw_n(n).expect("a deck only has 52 cards")
+}
+
+// Every distinct five-card hand of a full deck, all 2,598,960 of them, one at
+// a time.
+pub fn all_five_card_hands() -> impl Iterator<Item = [Card; 5]> {
+    use itertools::Itertools;
+
+    (0..52)
+        .filter_map(Card::from_index)
+        .combinations(5)
+        .map(|cards| cards.try_into().unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn seeded_shuffle_is_reproducible() {
+        use rand::{rngs::StdRng, SeedableRng};
+
+        fn order(deck: &Deck) -> Vec<(CardSuit, CardRank)> {
+            deck.cards.iter().map(|card| (card.suit, card.rank)).collect()
+        }
+
+        let mut unshuffled = Deck::empty();
+        unshuffled.reset();
+
+        let mut a = Deck::empty();
+        a.reset();
+        a.shuffle_with_rng(&mut StdRng::seed_from_u64(42));
+
+        let mut b = Deck::empty();
+        b.reset();
+        b.shuffle_with_rng(&mut StdRng::seed_from_u64(42));
+
+        assert_eq!(order(&a), order(&b));
+        assert_ne!(order(&a), order(&unshuffled));
+    }
+
+    #[test]
+    fn deck_from_seed() {
+        assert_eq!(Deck::shuffled_from_seed(43).cards, Deck::shuffled_from_seed(43).cards);
+        assert_eq!(Deck::shuffled_from_seed(43).remaining(), 52);
+
+        for seed in 0..20 {
+            assert_ne!(Deck::shuffled_from_seed(seed).cards, Deck::shuffled_from_seed(seed + 1).cards);
+        }
+    }
+
+    #[test]
+    fn default_deck_is_full() {
+        let deck = Deck::default();
+
+        assert_eq!(deck.remaining(), 52);
+        assert_eq!(deck.cards, Deck::new().cards);
+        assert_eq!(deck.cards, Deck::without(&[]).cards);
+        assert_eq!(Deck::empty().remaining(), 0);
+    }
+
+    #[test]
+    fn stripped_deck() {
+        use CardRank::*;
+
+        let mut deck = Deck::empty();
+        deck.reset_with_ranks(&[Ten, Jack, Queen, King, Ace]);
+
+        assert_eq!(deck.remaining(), 20);
+        assert!(deck.contains(&Card { suit: CardSuit::Clubs, rank: Ten }));
+        assert!(!deck.contains(&Card { suit: CardSuit::Clubs, rank: Nine }));
+
+        for _ in 0..20 {
+            assert!(deck.draw().is_some());
+        }
+
+        assert_eq!(deck.draw(), None);
+    }
+
+    #[test]
+    fn draw_multiple() {
+        let mut deck = Deck::empty();
+        deck.reset();
+
+        let top = deck.cards[deck.cards.len() - 1];
+        let flop = deck.draw_n(3).unwrap();
+
+        assert_eq!(flop.len(), 3);
+        assert_eq!((flop[0].suit, flop[0].rank), (top.suit, top.rank));
+        assert_eq!(deck.remaining(), 49);
+
+        assert!(deck.draw_n(50).is_none());
+        assert_eq!(deck.remaining(), 49);
+
+        assert_eq!(deck.draw_n(49).map(|cards| cards.len()), Some(49));
+        assert_eq!(deck.remaining(), 0);
+        assert_eq!(deck.draw_n(0), Some(vec![]));
+    }
+
+    #[test]
+    fn short_deck() {
+        let mut deck = Deck::empty();
+        deck.reset_short();
+
+        assert_eq!(deck.remaining(), 36);
+        assert!(deck.cards.iter().all(|card| card.rank >= CardRank::Six));
+    }
+
+    #[test]
+    fn random_cards_are_distinct() {
+        use rand::{rngs::StdRng, SeedableRng};
+
+        let mut rng = StdRng::seed_from_u64(29);
+        let cards = random_cards(52, &mut rng);
+
+        let distinct: std::collections::HashSet<Card> = cards.iter().copied().collect();
+
+        assert_eq!(distinct.len(), 52);
+        assert!(random_cards(0, &mut rng).is_empty());
+    }
+
+    #[test]
+    fn removing_cards() {
+        let ace = Card { suit: CardSuit::Spades, rank: CardRank::Ace };
+        let king = Card { suit: CardSuit::Spades, rank: CardRank::King };
+
+        let mut deck = Deck::generate();
+
+        assert!(deck.contains(&ace));
+        assert!(deck.remove(&ace));
+        assert!(!deck.remove(&ace));
+        assert!(!deck.contains(&ace));
+        assert_eq!(deck.remaining(), 51);
+
+        let deck = Deck::without(&[ace, king]);
+
+        assert_eq!(deck.remaining(), 50);
+        assert!(!deck.contains(&ace));
+        assert!(!deck.contains(&king));
+        assert!(deck.contains(&Card { suit: CardSuit::Hearts, rank: CardRank::Ace }));
+    }
+
+    #[test]
+    fn discard_and_draw() {
+        let mut deck = Deck::generate();
+        let mut hand = deck.draw_n(5).unwrap();
+        let before = hand.clone();
+
+        draw_replace(&mut hand, &[1, 3], &mut deck).unwrap();
+
+        assert_eq!(hand.len(), 5);
+        assert_eq!(deck.remaining(), 45);
+        assert_eq!(hand[0], before[0]);
+        assert_eq!(hand[2], before[2]);
+        assert_eq!(hand[4], before[4]);
+
+        // The replacements are new cards, and the discards are gone for good.
+        for card in [before[1], before[3]] {
+            assert!(!hand.contains(&card));
+            assert!(!deck.contains(&card));
+        }
+
+        assert_eq!(draw_replace(&mut hand, &[5], &mut deck), Err(DrawError::IndexOutOfRange(5)));
+        assert_eq!(draw_replace(&mut hand, &[2, 2], &mut deck), Err(DrawError::DuplicateIndex(2)));
+
+        deck.draw_n(44).unwrap();
+
+        assert_eq!(draw_replace(&mut hand, &[0, 1], &mut deck), Err(DrawError::DeckExhausted));
+        assert_eq!(deck.remaining(), 1);
+    }
+
+    #[test]
+    fn enumerating_hands() {
+        use crate::hand::Hand;
+
+        let hands: Vec<[Card; 5]> = all_five_card_hands().take(100_000).collect();
+
+        assert_eq!(hands.len(), 100_000);
+
+        for cards in &hands {
+            Hand::try_new(*cards).unwrap();
+        }
+    }
+
+    #[cfg(feature = "exhaustive")]
+    #[test]
+    fn four_royal_flushes() {
+        use crate::hand::{Hand, HandCategory};
+
+        let royals = all_five_card_hands()
+            .filter(|&cards| Hand::new(cards).category() == HandCategory::RoyalFlush)
+            .count();
+
+        assert_eq!(all_five_card_hands().count(), 2_598_960);
+        assert_eq!(royals, 4);
+    }
+}
@@ -0,0 +1,669 @@
+use itertools::Itertools;
+use rand::seq::SliceRandom;
+use rand::RngCore;
+
+use crate::card::Card;
+use crate::deck::Deck;
+use crate::eval::{evaluate_7, HandRank};
+use crate::hand::{form_best_hand, HandCategory};
+
+#[derive(PartialEq, Clone, Copy, Debug, Default)]
+pub struct Equity {
+    pub win: f64,
+    pub tie: f64,
+    pub loss: f64
+}
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum EquityError {
+    // The same card was given more than once.
+    DuplicateCard(Card),
+    // The board must hold 0, 3, 4 or 5 cards.
+    BadBoardSize(usize),
+    // The deck cannot deal every opponent a hand and complete the board.
+    NotEnoughCards { needed: usize, available: usize }
+}
+
+impl std::fmt::Display for EquityError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EquityError::DuplicateCard(card) => write!(f, "card {} is used more than once", card),
+            EquityError::BadBoardSize(n) => write!(f, "a board cannot have {} cards", n),
+            EquityError::NotEnoughCards { needed, available } => {
+                write!(f, "{} cards are needed but only {} are left", needed, available)
+            }
+        }
+    }
+}
+
+impl std::error::Error for EquityError {}
+
+// Running win/tie/loss counts, turned into fractions once all outcomes are in.
+#[derive(Clone, Copy, Debug, Default)]
+struct Tally {
+    win: u64,
+    tie: u64,
+    loss: u64
+}
+
+impl Tally {
+    fn record(&mut self, ordering: std::cmp::Ordering) {
+        use std::cmp::Ordering::*;
+
+        match ordering {
+            Greater => self.win += 1,
+            Equal   => self.tie += 1,
+            Less    => self.loss += 1
+        }
+    }
+
+    #[cfg(feature = "rayon")]
+    fn merge(self, other: Tally) -> Tally {
+        Tally {
+            win: self.win + other.win,
+            tie: self.tie + other.tie,
+            loss: self.loss + other.loss
+        }
+    }
+
+    fn equity(&self) -> Equity {
+        let total = (self.win + self.tie + self.loss) as f64;
+
+        if total == 0.0 {
+            return Equity::default();
+        }
+
+        Equity {
+            win: self.win as f64 / total,
+            tie: self.tie as f64 / total,
+            loss: self.loss as f64 / total
+        }
+    }
+}
+
+// All cards of a full deck that are not among 'known'. Cards may be listed
+// in 'known' more than once.
+pub fn unseen_cards(known: &[Card]) -> Vec<Card> {
+    let mut deck = Deck::empty();
+    deck.reset();
+
+    let mut cards = Vec::with_capacity(52);
+
+    while let Some(card) = deck.draw() {
+        if !known.contains(&card) {
+            cards.push(card);
+        }
+    }
+
+    cards
+}
+
+pub fn unseen_count(known: &[Card]) -> usize {
+    unseen_cards(known).len()
+}
+
+// The rank of the best hand made of two hole cards and a complete board.
+fn showdown_rank(board: &[Card], hole: &[Card]) -> HandRank {
+    let mut cards = [hole[0]; 7];
+
+    cards[..5].copy_from_slice(board);
+    cards[5..].copy_from_slice(hole);
+
+    evaluate_7(&cards)
+}
+
+// Plays 'trials' random completions of the board against an opponent holding
+// two random cards.
+fn simulate_vs_random(
+    hole: [Card; 2],
+    board: &[Card],
+    trials: u32,
+    rng: &mut impl RngCore
+) -> Tally
+{
+    assert!(board.len() <= 5, "a board has at most five cards");
+
+    let known: Vec<Card> = hole.iter().chain(board).copied().collect();
+    let mut stub = unseen_cards(&known);
+    let missing = 5 - board.len();
+    let mut tally = Tally::default();
+
+    for _ in 0..trials {
+        // Only the first 'missing + 2' cards need to be randomized.
+        let (dealt, _) = stub.partial_shuffle(rng, missing + 2);
+        let (opponent, rest) = dealt.split_at(2);
+
+        let full_board: Vec<Card> = board.iter().chain(rest).copied().collect();
+
+        let ours = showdown_rank(&full_board, &hole);
+        let theirs = showdown_rank(&full_board, opponent);
+
+        tally.record(ours.cmp(&theirs));
+    }
+
+    tally
+}
+
+// Plays 'trials' random completions of the board against an opponent holding
+// a combination drawn from 'range'.
+fn simulate_vs_range(
+    hole: [Card; 2],
+    range: &[[Card; 2]],
+    board: &[Card],
+    trials: u32,
+    rng: &mut impl RngCore
+) -> Tally
+{
+    assert!(board.len() <= 5, "a board has at most five cards");
+
+    let known: Vec<Card> = hole.iter().chain(board).copied().collect();
+
+    let range: Vec<[Card; 2]> = range.iter()
+        .filter(|combo| !combo.iter().any(|card| known.contains(card)))
+        .copied()
+        .collect();
+
+    let mut tally = Tally::default();
+
+    if range.is_empty() {
+        return tally;
+    }
+
+    let mut stub = unseen_cards(&known);
+    let missing = 5 - board.len();
+
+    for _ in 0..trials {
+        let opponent = range.choose(rng).unwrap();
+
+        // At most two of the shuffled cards belong to the opponent, which
+        // leaves enough to complete the board.
+        let (dealt, _) = stub.partial_shuffle(rng, missing + 2);
+
+        let full_board: Vec<Card> = board.iter()
+            .chain(dealt.iter().filter(|card| !opponent.contains(card)).take(missing))
+            .copied()
+            .collect();
+
+        let ours = showdown_rank(&full_board, &hole);
+        let theirs = showdown_rank(&full_board, opponent);
+
+        tally.record(ours.cmp(&theirs));
+    }
+
+    tally
+}
+
+// Estimates the equity of 'hole' against a single opponent holding two random
+// cards by dealing out 'trials' random completions of the board.
+pub fn equity_vs_random(
+    hole: [Card; 2],
+    board: &[Card],
+    trials: u32,
+    rng: &mut impl RngCore
+) -> Equity
+{
+    simulate_vs_random(hole, board, trials, rng).equity()
+}
+
+// Estimates the equity of 'hole' against an opponent holding a combination
+// drawn uniformly from 'range', e.g. one made with 'parse_range'. Combinations
+// that share a card with 'hole' or 'board' are never drawn; if every one of
+// them does, the equity is all zeros.
+pub fn equity_vs_range(
+    hole: [Card; 2],
+    range: &[[Card; 2]],
+    board: &[Card],
+    trials: u32,
+    rng: &mut impl RngCore
+) -> Equity
+{
+    simulate_vs_range(hole, range, board, trials, rng).equity()
+}
+
+// Estimates the equity of 'hero' against 'num_opponents' players holding two
+// random cards each. A trial is won only if no opponent matches the hero's
+// hand, and tied if the best opponents do no better than match it.
+pub fn equity_multiway(
+    hero: [Card; 2],
+    num_opponents: usize,
+    board: &[Card],
+    trials: u32,
+    rng: &mut impl RngCore
+) -> Result<Equity, EquityError>
+{
+    if board.len() > 5 {
+        return Err(EquityError::BadBoardSize(board.len()));
+    }
+
+    let known: Vec<Card> = hero.iter().chain(board).copied().collect();
+    let mut stub = unseen_cards(&known);
+    let missing = 5 - board.len();
+    let needed = 2 * num_opponents + missing;
+
+    if needed > stub.len() {
+        return Err(EquityError::NotEnoughCards { needed, available: stub.len() });
+    }
+
+    let mut tally = Tally::default();
+
+    for _ in 0..trials {
+        let (dealt, _) = stub.partial_shuffle(rng, needed);
+        let (opponents, rest) = dealt.split_at(2 * num_opponents);
+
+        let full_board: Vec<Card> = board.iter().chain(rest).copied().collect();
+
+        let ours = showdown_rank(&full_board, &hero);
+
+        let best_opponent = opponents.chunks(2)
+            .map(|opponent| showdown_rank(&full_board, opponent))
+            .max();
+
+        match best_opponent {
+            Some(theirs) => tally.record(ours.cmp(&theirs)),
+            None         => tally.record(std::cmp::Ordering::Greater)
+        }
+    }
+
+    Ok(tally.equity())
+}
+
+// Trials are run in chunks of this size, each with its own generator seeded
+// from the base seed and the chunk's index. The result is therefore the same
+// no matter how many threads share the work.
+#[cfg(feature = "rayon")]
+const CHUNK_TRIALS: u32 = 4096;
+
+#[cfg(feature = "rayon")]
+fn par_simulate<F>(trials: u32, seed: u64, simulate: F) -> Tally
+where
+    F: Fn(u32, &mut rand::rngs::StdRng) -> Tally + Sync
+{
+    use rand::SeedableRng;
+    use rayon::prelude::*;
+
+    (0..trials.div_ceil(CHUNK_TRIALS))
+        .into_par_iter()
+        .map(|chunk| {
+            let trials = CHUNK_TRIALS.min(trials - chunk * CHUNK_TRIALS);
+            let mut rng = rand::rngs::StdRng::seed_from_u64(seed.wrapping_add(chunk as u64));
+
+            simulate(trials, &mut rng)
+        })
+        .reduce(Tally::default, Tally::merge)
+}
+
+// Like 'equity_vs_random', with the trials spread across threads.
+#[cfg(feature = "rayon")]
+pub fn par_equity_vs_random(hole: [Card; 2], board: &[Card], trials: u32, seed: u64) -> Equity {
+    par_simulate(trials, seed, |trials, rng| simulate_vs_random(hole, board, trials, rng)).equity()
+}
+
+// Like 'equity_vs_range', with the trials spread across threads.
+#[cfg(feature = "rayon")]
+pub fn par_equity_vs_range(
+    hole: [Card; 2],
+    range: &[[Card; 2]],
+    board: &[Card],
+    trials: u32,
+    seed: u64
+) -> Equity
+{
+    par_simulate(trials, seed, |trials, rng| {
+        simulate_vs_range(hole, range, board, trials, rng)
+    })
+    .equity()
+}
+
+// Computes the exact equity of 'hole' against 'opp' by evaluating every
+// possible completion of the board.
+pub fn equity_exact(
+    hole: [Card; 2],
+    opp: [Card; 2],
+    board: &[Card]
+) -> Result<Equity, EquityError>
+{
+    if !matches!(board.len(), 0 | 3 | 4 | 5) {
+        return Err(EquityError::BadBoardSize(board.len()));
+    }
+
+    let known: Vec<Card> = hole.iter().chain(&opp).chain(board).copied().collect();
+
+    for (i, a) in known.iter().enumerate() {
+        if known[i + 1..].contains(a) {
+            return Err(EquityError::DuplicateCard(*a));
+        }
+    }
+
+    let stub = unseen_cards(&known);
+    let mut tally = Tally::default();
+
+    for rest in stub.into_iter().combinations(5 - board.len()) {
+        let full_board: Vec<Card> = board.iter().copied().chain(rest).collect();
+
+        let ours = showdown_rank(&full_board, &hole);
+        let theirs = showdown_rank(&full_board, &opp);
+
+        tally.record(ours.cmp(&theirs));
+    }
+
+    Ok(tally.equity())
+}
+
+#[derive(PartialEq, Eq, Clone, Debug, Default)]
+pub struct OutsReport {
+    pub count: usize,
+    // The improving cards, keyed by the category they improve the hand to.
+    pub by_category: std::collections::BTreeMap<HandCategory, Vec<Card>>
+}
+
+// Finds the unseen cards that would improve the category of the best hand
+// made of 'hole' and 'board' if dealt next.
+pub fn count_outs(hole: &[Card], board: &[Card]) -> OutsReport {
+    let mut report = OutsReport::default();
+
+    let current = match form_best_hand(board, hole) {
+        Some(hand) => hand.category(),
+        None => return report
+    };
+
+    let known: Vec<Card> = hole.iter().chain(board).copied().collect();
+    let mut next_board = board.to_vec();
+
+    for card in unseen_cards(&known) {
+        next_board.push(card);
+
+        let category = form_best_hand(&next_board, hole).unwrap().category();
+
+        if category > current {
+            report.count += 1;
+            report.by_category.entry(category).or_default().push(card);
+        }
+
+        next_board.pop();
+    }
+
+    report
+}
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum HandState {
+    // A pair or better.
+    Made(HandCategory),
+    FlushDraw,
+    // Two ranks complete a straight, which includes double gutshots.
+    OpenEndedStraightDraw,
+    // A single rank completes a straight.
+    GutshotDraw,
+    // A flush draw that is also a straight draw.
+    ComboDraw,
+    Nothing
+}
+
+// Whether 'hole' has made a pair or better with 'board', and otherwise what it
+// might draw to with the next card.
+pub fn classify_texture(hole: &[Card], board: &[Card]) -> HandState {
+    let current = match form_best_hand(board, hole) {
+        Some(hand) => hand.category(),
+        None => return HandState::Nothing
+    };
+
+    if current != HandCategory::HighCard {
+        return HandState::Made(current);
+    }
+
+    // No more cards are coming on the river.
+    if board.len() >= 5 {
+        return HandState::Nothing;
+    }
+
+    let outs = count_outs(hole, board);
+
+    let outs_to = |categories: &[HandCategory]| -> Vec<Card> {
+        categories.iter()
+            .filter_map(|category| outs.by_category.get(category))
+            .flatten()
+            .copied()
+            .collect()
+    };
+
+    use HandCategory::*;
+
+    let flush_draw = !outs_to(&[Flush, StraightFlush, RoyalFlush]).is_empty();
+
+    let straight_ranks = outs_to(&[Straight, StraightFlush, RoyalFlush]).iter()
+        .map(|card| card.rank)
+        .unique()
+        .count();
+
+    match (flush_draw, straight_ranks) {
+        (true, 0)  => HandState::FlushDraw,
+        (true, _)  => HandState::ComboDraw,
+        (false, 0) => HandState::Nothing,
+        (false, 1) => HandState::GutshotDraw,
+        (false, _) => HandState::OpenEndedStraightDraw
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::card::{CardRank, CardSuit};
+    use rand::{rngs::StdRng, SeedableRng};
+    use CardRank::*;
+    use CardSuit::*;
+
+    #[test]
+    fn pocket_aces_vs_random() {
+        let hole = [
+            Card { suit: Hearts, rank: Ace },
+            Card { suit: Spades, rank: Ace }
+        ];
+
+        let mut rng = StdRng::seed_from_u64(7);
+        let equity = equity_vs_random(hole, &[], 10_000, &mut rng);
+
+        assert!((equity.win - 0.85).abs() < 0.02, "{:?}", equity);
+        assert!((equity.win + equity.tie + equity.loss - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn multiway_equity() {
+        let hole = [
+            Card { suit: Hearts, rank: Ten },
+            Card { suit: Spades, rank: Ten }
+        ];
+
+        let mut rng = StdRng::seed_from_u64(7);
+
+        let heads_up = equity_multiway(hole, 1, &[], 5000, &mut rng).unwrap();
+        let full_ring = equity_multiway(hole, 8, &[], 5000, &mut rng).unwrap();
+
+        assert!((heads_up.win - 0.75).abs() < 0.03, "{:?}", heads_up);
+        assert!(full_ring.win < heads_up.win / 2.0, "{:?}", full_ring);
+
+        // 23 opponents would need 46 of the 50 cards left, plus the board.
+        assert!(equity_multiway(hole, 22, &[], 1, &mut rng).is_ok());
+        assert_eq!(
+            equity_multiway(hole, 23, &[], 1, &mut rng),
+            Err(EquityError::NotEnoughCards { needed: 51, available: 50 })
+        );
+    }
+
+    #[test]
+    fn aces_vs_kings_exact() {
+        let aces = [
+            Card { suit: Hearts, rank: Ace },
+            Card { suit: Spades, rank: Ace }
+        ];
+
+        let kings = [
+            Card { suit: Diamonds, rank: King },
+            Card { suit: Clubs, rank: King }
+        ];
+
+        let equity = equity_exact(aces, kings, &[]).unwrap();
+
+        // Published figures for this matchup: 81.06% win, 0.38% tie.
+        assert!((equity.win - 0.8106).abs() < 0.001, "{:?}", equity);
+        assert!((equity.tie - 0.0038).abs() < 0.001, "{:?}", equity);
+    }
+
+    #[test]
+    fn aces_vs_range() {
+        use crate::range::parse_range;
+
+        let aces = [
+            Card { suit: Spades, rank: Ace },
+            Card { suit: Hearts, rank: Ace }
+        ];
+
+        let mut rng = StdRng::seed_from_u64(39);
+        let range = parse_range("KK, QQ").unwrap();
+        let equity = equity_vs_range(aces, &range, &[], 10_000, &mut rng);
+
+        assert!(equity.win > 0.78, "{:?}", equity);
+
+        // Every combination of this range holds a card that is already known.
+        let board = cards("Kh Qs 7c 3d 2h");
+        let blocked: Vec<[Card; 2]> = cards("Kh Ks Kh Kc Qs Qd").chunks(2)
+            .map(|combo| [combo[0], combo[1]])
+            .collect();
+
+        assert_eq!(equity_vs_range(aces, &blocked, &board, 100, &mut rng), Equity::default());
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn parallel_equity() {
+        let aces = [
+            Card { suit: Spades, rank: Ace },
+            Card { suit: Hearts, rank: Ace }
+        ];
+
+        let with_threads = |threads| {
+            rayon::ThreadPoolBuilder::new()
+                .num_threads(threads)
+                .build()
+                .unwrap()
+                .install(|| par_equity_vs_random(aces, &[], 50_000, 40))
+        };
+
+        let parallel = with_threads(4);
+
+        assert_eq!(parallel, with_threads(1));
+
+        let serial = equity_vs_random(aces, &[], 50_000, &mut StdRng::seed_from_u64(40));
+
+        assert!((parallel.win - serial.win).abs() < 0.01, "{:?} {:?}", parallel, serial);
+        assert!((parallel.tie - serial.tie).abs() < 0.01, "{:?} {:?}", parallel, serial);
+
+        let range = crate::range::parse_range("KK, QQ").unwrap();
+        let parallel = par_equity_vs_range(aces, &range, &[], 50_000, 40);
+        let serial = equity_vs_range(aces, &range, &[], 50_000, &mut StdRng::seed_from_u64(40));
+
+        assert!((parallel.win - serial.win).abs() < 0.01, "{:?} {:?}", parallel, serial);
+    }
+
+    #[test]
+    fn exact_equity_input_validation() {
+        let aces = [
+            Card { suit: Hearts, rank: Ace },
+            Card { suit: Spades, rank: Ace }
+        ];
+
+        let kings = [
+            Card { suit: Diamonds, rank: King },
+            Card { suit: Clubs, rank: King }
+        ];
+
+        let board = [Card { suit: Hearts, rank: Two }];
+
+        assert_eq!(equity_exact(aces, kings, &board), Err(EquityError::BadBoardSize(1)));
+
+        let board = [
+            Card { suit: Hearts, rank: Two },
+            Card { suit: Spades, rank: Ace },
+            Card { suit: Clubs, rank: Nine }
+        ];
+
+        assert_eq!(
+            equity_exact(aces, kings, &board),
+            Err(EquityError::DuplicateCard(Card { suit: Spades, rank: Ace }))
+        );
+
+        let board = [
+            Card { suit: Hearts, rank: Two },
+            Card { suit: Spades, rank: Seven },
+            Card { suit: Clubs, rank: Nine },
+            Card { suit: Clubs, rank: Ace },
+            Card { suit: Clubs, rank: Three }
+        ];
+
+        let equity = equity_exact(aces, kings, &board).unwrap();
+
+        assert_eq!(equity, Equity { win: 1.0, tie: 0.0, loss: 0.0 });
+    }
+
+    fn cards(codes: &str) -> Vec<Card> {
+        crate::card::parse_cards(codes).unwrap()
+    }
+
+    #[test]
+    fn unseen_card_counts() {
+        let known = cards("Ah Kh Qs 7c 7d 2h 9s");
+        let unseen = unseen_cards(&known);
+
+        assert_eq!(unseen.len(), 45);
+        assert_eq!(unseen_count(&known), 45);
+        assert!(unseen.iter().all(|card| !known.contains(card)));
+
+        // Repeated known cards only count once.
+        assert_eq!(unseen_count(&cards("Ah Ah Kh")), 50);
+        assert_eq!(unseen_count(&[]), 52);
+    }
+
+    #[test]
+    fn open_ended_straight_draw_outs() {
+        let report = count_outs(&cards("9c 8d"), &cards("7h 6s Kc"));
+
+        let straights = &report.by_category[&HandCategory::Straight];
+
+        assert_eq!(straights.len(), 8);
+        assert!(straights.iter().all(|card| matches!(card.rank, CardRank::Ten | CardRank::Five)));
+
+        // Pairing any of the cards also improves a high card hand.
+        assert_eq!(report.by_category[&HandCategory::Pair].len(), 15);
+        assert_eq!(report.count, 23);
+    }
+
+    #[test]
+    fn flush_draw_outs() {
+        let report = count_outs(&cards("Ah 2h"), &cards("7h Th Kc"));
+
+        assert_eq!(report.by_category[&HandCategory::Flush].len(), 9);
+
+        // With a straight already made only the flush cards improve.
+        let report = count_outs(&cards("9h 8h"), &cards("7h 6h 5c"));
+
+        assert_eq!(report.count, 9);
+        assert_eq!(report.by_category[&HandCategory::Flush].len(), 7);
+        assert_eq!(report.by_category[&HandCategory::StraightFlush].len(), 2);
+    }
+
+    #[test]
+    fn made_hands_and_draws() {
+        assert_eq!(classify_texture(&cards("Ah 2h"), &cards("7h Th Kc")), HandState::FlushDraw);
+        assert_eq!(classify_texture(&cards("9h 8h"), &cards("7h 6c Kh")), HandState::ComboDraw);
+        assert_eq!(classify_texture(&cards("9c 8d"), &cards("7h 6s Kc")), HandState::OpenEndedStraightDraw);
+        assert_eq!(classify_texture(&cards("9c 8d"), &cards("7h 5s Kc")), HandState::GutshotDraw);
+        assert_eq!(classify_texture(&cards("9c 2d"), &cards("7h 5s Kc")), HandState::Nothing);
+
+        assert_eq!(
+            classify_texture(&cards("Kd 8h"), &cards("7h 6h Kh")),
+            HandState::Made(HandCategory::Pair)
+        );
+
+        // Draws are dead once the river is out.
+        assert_eq!(classify_texture(&cards("Ah 2h"), &cards("7h Th Kc 3s 4d")), HandState::Nothing);
+    }
+}
@@ -0,0 +1,314 @@
+use std::collections::HashMap;
+
+use itertools::Itertools;
+
+use crate::card::{Card, CardRank, CardSuit};
+use crate::hand::{Hand, HandCategory};
+
+// A comparable score for the best five-card hand out of a set of cards.
+// Scores order exactly like the corresponding 'Hand's: the category sits in
+// the high bits, followed by the ranks of the five significance-ordered cards
+// at four bits each.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Debug)]
+pub struct HandRank(u32);
+
+impl HandRank {
+    // 'ranks' are rank ordinals (Two is 0, Ace is 12) in significance order.
+    pub(crate) fn new(category: HandCategory, ranks: [u8; 5]) -> HandRank {
+        let score = ranks.iter()
+            .fold(category as u32, |score, &rank| score << 4 | rank as u32);
+
+        HandRank(score)
+    }
+
+    pub fn category(&self) -> HandCategory {
+        use HandCategory::*;
+
+        const CATEGORIES: [HandCategory; 10] = [
+            HighCard,
+            Pair,
+            TwoPair,
+            ThreeOfAKind,
+            Straight,
+            Flush,
+            FullHouse,
+            FourOfAKind,
+            StraightFlush,
+            RoyalFlush
+        ];
+
+        CATEGORIES[(self.0 >> 20) as usize]
+    }
+
+    pub fn score(&self) -> u32 {
+        self.0
+    }
+
+    // The rank ordinals of the five cards in significance order.
+    pub(crate) fn ranks(&self) -> [u8; 5] {
+        std::array::from_fn(|i| (self.0 >> (16 - 4 * i) & 0xf) as u8)
+    }
+}
+
+// Rank ordinal of the highest card of the best straight in 'mask', where bit
+// 'n' is set if a card of rank ordinal 'n' is present.
+fn straight_high(mask: u16) -> Option<u8> {
+    // Shift everything up by one to make room for the ace below the two.
+    let mask = (mask as u32) << 1 | (mask as u32 >> 12) & 1;
+
+    (4..=13u8).rev()
+        .find(|&top| {
+            let run = 0b11111 << (top - 4);
+            mask & run == run
+        })
+        .map(|top| top - 1)
+}
+
+// The ranks of a straight with the given high card, the ace playing low in
+// the five-high straight.
+fn straight_ranks(high: u8) -> [u8; 5] {
+    std::array::from_fn(|i| (high + 13 - i as u8) % 13)
+}
+
+// Rank ordinals present in 'mask', highest first.
+fn ranks_desc(mask: u16) -> impl Iterator<Item = u8> {
+    (0..13u8).rev().filter(move |&rank| mask & 1 << rank != 0)
+}
+
+// Evaluates the best five-card hand of seven cards without generating the
+// 21 five-card combinations.
+pub fn evaluate_7(cards: &[Card; 7]) -> HandRank {
+    use HandCategory::*;
+
+    // Rank bitmasks per suit and the number of cards of each rank.
+    let mut suits = [0u16; 4];
+    let mut counts = [0u8; 13];
+
+    for card in cards {
+        suits[card.suit as usize] |= 1 << card.rank as u8;
+        counts[card.rank as usize] += 1;
+    }
+
+    let all = suits.iter().fold(0, |all, &mask| all | mask);
+
+    // Rank masks of the ranks held at least 'n' times.
+    let mut at_least = [0u16; 5];
+
+    for (rank, &count) in counts.iter().enumerate() {
+        for mask in &mut at_least[1..=count as usize] {
+            *mask |= 1 << rank;
+        }
+    }
+
+    let flush = suits.iter().find(|mask| mask.count_ones() >= 5).copied();
+
+    if let Some(mask) = flush {
+        if let Some(high) = straight_high(mask) {
+            let category = if high == 12 { RoyalFlush } else { StraightFlush };
+
+            return HandRank::new(category, straight_ranks(high));
+        }
+    }
+
+    if let Some(quads) = ranks_desc(at_least[4]).next() {
+        let kicker = ranks_desc(all & !(1 << quads)).next().unwrap();
+
+        return HandRank::new(FourOfAKind, [quads, quads, quads, quads, kicker]);
+    }
+
+    if let Some(trips) = ranks_desc(at_least[3]).next() {
+        if let Some(pair) = ranks_desc(at_least[2] & !(1 << trips)).next() {
+            return HandRank::new(FullHouse, [trips, trips, trips, pair, pair]);
+        }
+    }
+
+    if let Some(mask) = flush {
+        let mut ranks = ranks_desc(mask);
+
+        return HandRank::new(Flush, std::array::from_fn(|_| ranks.next().unwrap()));
+    }
+
+    if let Some(high) = straight_high(all) {
+        return HandRank::new(Straight, straight_ranks(high));
+    }
+
+    if let Some(trips) = ranks_desc(at_least[3]).next() {
+        let mut kickers = ranks_desc(all & !(1 << trips));
+        let (a, b) = (kickers.next().unwrap(), kickers.next().unwrap());
+
+        return HandRank::new(ThreeOfAKind, [trips, trips, trips, a, b]);
+    }
+
+    let mut pairs = ranks_desc(at_least[2]);
+
+    if let Some(high_pair) = pairs.next() {
+        if let Some(low_pair) = pairs.next() {
+            let kicker = ranks_desc(all & !(1 << high_pair | 1 << low_pair)).next().unwrap();
+
+            return HandRank::new(TwoPair, [high_pair, high_pair, low_pair, low_pair, kicker]);
+        }
+
+        let mut kickers = ranks_desc(all & !(1 << high_pair));
+        let (a, b, c) = (kickers.next().unwrap(), kickers.next().unwrap(), kickers.next().unwrap());
+
+        return HandRank::new(Pair, [high_pair, high_pair, a, b, c]);
+    }
+
+    let mut ranks = ranks_desc(all);
+
+    HandRank::new(HighCard, std::array::from_fn(|_| ranks.next().unwrap()))
+}
+
+// A prime for each rank ordinal. The product of a hand's primes names its
+// ranks regardless of their order.
+const RANK_PRIMES: [u32; 13] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41];
+
+// The class of every rank product, for flushes and for all other hands.
+struct RankClassTable {
+    flushes: HashMap<u32, u16>,
+    others: HashMap<u32, u16>
+}
+
+impl RankClassTable {
+    fn build() -> RankClassTable {
+        let ranks = CardRank::all();
+
+        // Five distinct ranks can be suited or not; any other ranks cannot.
+        let flushes = ranks.iter().copied().combinations(5).map(|ranks| (true, ranks));
+
+        let others = ranks.iter()
+            .copied()
+            .combinations_with_replacement(5)
+            .filter(|ranks| ranks.iter().dedup_with_count().all(|(count, _)| count <= 4))
+            .map(|ranks| (false, ranks));
+
+        // A hand of each class, strongest first.
+        let classes: Vec<(bool, Vec<CardRank>, u32)> = flushes.chain(others)
+            .map(|(flush, ranks)| {
+                let suits = CardSuit::all();
+
+                // Suits cycle so that equal ranks differ, and five of them
+                // only share a suit when the hand is meant to be a flush.
+                let cards: Vec<Card> = ranks.iter()
+                    .enumerate()
+                    .map(|(i, &rank)| Card { suit: if flush { suits[0] } else { suits[i % 4] }, rank })
+                    .collect();
+
+                let strength = Hand::new(cards.try_into().unwrap()).strength();
+
+                (flush, ranks, strength)
+            })
+            .sorted_unstable_by_key(|&(_, _, strength)| std::cmp::Reverse(strength))
+            .collect();
+
+        let mut table = RankClassTable { flushes: HashMap::new(), others: HashMap::new() };
+
+        for (class, (flush, ranks, _)) in classes.into_iter().enumerate() {
+            let product = ranks.iter().map(|&rank| RANK_PRIMES[rank as usize]).product();
+
+            match flush {
+                true  => table.flushes.insert(product, class as u16 + 1),
+                false => table.others.insert(product, class as u16 + 1)
+            };
+        }
+
+        table
+    }
+}
+
+// The number of classes of five-card hands that differ in strength.
+pub const HAND_RANK_CLASSES: u16 = 7462;
+
+// The equivalence class of a five-card hand, from 1 for a royal flush to
+// 7462 for seven-high. Lower is better, and hands compare like their classes
+// do. The classes are found by looking up the product of a prime per rank, so
+// the cards need no sorting. The cards must be distinct: a repeated card is
+// either mistaken for a pair or, in a flush or five of a kind, matches no
+// class and panics.
+pub fn hand_rank_class(cards: &[Card; 5]) -> u16 {
+    use std::sync::OnceLock;
+
+    static TABLE: OnceLock<RankClassTable> = OnceLock::new();
+
+    let table = TABLE.get_or_init(RankClassTable::build);
+
+    let product: u32 = cards.iter().map(|card| RANK_PRIMES[card.rank as usize]).product();
+    let flush = cards.iter().all(|card| card.suit == cards[0].suit);
+
+    let classes = if flush { &table.flushes } else { &table.others };
+
+    *classes.get(&product).expect("a hand without repeated cards")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::deck::{random_cards, Deck};
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn agrees_with_combination_scan() {
+        let mut rng = StdRng::seed_from_u64(21);
+        let mut deck = Deck::empty();
+
+        for _ in 0..10_000 {
+            deck.reset();
+            deck.shuffle_with_rng(&mut rng);
+
+            let cards: [Card; 7] = deck.draw_n(7).unwrap().try_into().unwrap();
+
+            let hand = cards.iter()
+                .copied()
+                .combinations(5)
+                .map(|cards| Hand::new(cards.try_into().unwrap()))
+                .max()
+                .unwrap();
+
+            assert_eq!(evaluate_7(&cards), HandRank::from(&hand), "{:?}", cards);
+        }
+    }
+
+    #[test]
+    fn wheel_and_royal() {
+        let cards: Vec<Card> = ["Ah", "2c", "3d", "4s", "5h", "Kd", "Kc"].iter()
+            .map(|code| code.parse().unwrap())
+            .collect();
+
+        let rank = evaluate_7(&cards.try_into().unwrap());
+
+        assert_eq!(rank.category(), HandCategory::Straight);
+        assert_eq!(rank, HandRank::new(HandCategory::Straight, [3, 2, 1, 0, 12]));
+
+        let cards: Vec<Card> = ["Ah", "Kh", "Qh", "Jh", "Th", "9h", "Ac"].iter()
+            .map(|code| code.parse().unwrap())
+            .collect();
+
+        assert_eq!(evaluate_7(&cards.try_into().unwrap()).category(), HandCategory::RoyalFlush);
+    }
+
+    #[test]
+    fn rank_classes_order_like_hands() {
+        let class = |codes: &str| {
+            let cards: Vec<Card> = crate::card::parse_cards(codes).unwrap();
+            hand_rank_class(&cards.try_into().unwrap())
+        };
+
+        assert_eq!(class("Ah Kh Qh Jh Th"), 1);
+        assert_eq!(class("Ks Qs Js Ts 9s"), 2);
+        assert_eq!(class("7d 5c 4s 3h 2d"), HAND_RANK_CLASSES);
+        assert_eq!(class("Ad Kc Qs Jh Th"), class("As Kd Qc Jc Ts"));
+
+        let mut rng = StdRng::seed_from_u64(96);
+
+        for _ in 0..20_000 {
+            let a: [Card; 5] = random_cards(5, &mut rng).try_into().unwrap();
+            let b: [Card; 5] = random_cards(5, &mut rng).try_into().unwrap();
+
+            assert_eq!(
+                hand_rank_class(&a).cmp(&hand_rank_class(&b)),
+                Hand::new(b).cmp(&Hand::new(a)),
+                "{:?} {:?}", a, b
+            );
+        }
+    }
+}
@@ -0,0 +1,1582 @@
+use std::collections::BTreeMap;
+
+use itertools::Itertools;
+use rand::{rngs::StdRng, SeedableRng};
+
+use crate::betting::{Action, BettingRound};
+use crate::card::Card;
+use crate::deck::Deck;
+use crate::hand::{form_best_hand, Hand};
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PlayerStatus {
+    Active,
+    Folded,
+    AllIn
+}
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum BetError {
+    // Only active players can bet.
+    NotActive(PlayerStatus)
+}
+
+impl std::fmt::Display for BetError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BetError::NotActive(status) => write!(f, "a player who is {:?} cannot bet", status)
+        }
+    }
+}
+
+impl std::error::Error for BetError {}
+
+pub struct Player {
+    pub id: PlayerId,
+    pub name: String,
+    pub money: u32,
+    pub hole_cards: Vec<Card>,
+    pub status: PlayerStatus
+}
+
+impl Player {
+    pub fn new(id: PlayerId, name: &str, money: u32) -> Player {
+        Player {
+            id,
+            name: name.to_string(),
+            money,
+            hole_cards: Vec::with_capacity(2),
+            status: PlayerStatus::Active
+        }
+    }
+
+    // Moves up to 'amount' chips out of the player's stack and returns how
+    // many were actually committed. Betting the whole stack puts the player
+    // all-in.
+    pub fn bet(&mut self, amount: u32) -> Result<u32, BetError> {
+        if self.status != PlayerStatus::Active {
+            return Err(BetError::NotActive(self.status));
+        }
+
+        let committed = amount.min(self.money);
+
+        self.money -= committed;
+
+        if self.money == 0 {
+            self.status = PlayerStatus::AllIn;
+        }
+
+        Ok(committed)
+    }
+
+    pub fn fold(&mut self) {
+        self.status = PlayerStatus::Folded;
+    }
+
+    pub fn is_busted(&self) -> bool {
+        self.money == 0
+    }
+
+    pub fn stack_in_bb(&self, big_blind: u32) -> f64 {
+        stack_in_bb(self.money, big_blind)
+    }
+}
+
+// The size of a stack in big blinds. Without a big blind any stack is
+// infinitely deep.
+pub fn stack_in_bb(money: u32, big_blind: u32) -> f64 {
+    if big_blind == 0 {
+        return f64::INFINITY;
+    }
+
+    money as f64 / big_blind as f64
+}
+
+// The most that can change hands between 'a' and 'b' heads-up.
+pub fn effective_stack(a: &Player, b: &Player) -> u32 {
+    a.money.min(b.money)
+}
+
+// The most that any two of 'players' can lose to each other, which is the
+// second-largest stack: the biggest stack can only win what its deepest
+// opponent has. Fewer than two players have nothing at risk.
+pub fn effective_stack_multiway(players: &[&Player]) -> u32 {
+    let mut stacks: Vec<u32> = players.iter().map(|player| player.money).collect();
+
+    stacks.sort_unstable_by(|a, b| b.cmp(a));
+    stacks.get(1).copied().unwrap_or(0)
+}
+
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PlayerId(pub u32);
+
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct Pot {
+    pub size: u32,
+    // Players who may win the pot.
+    pub eligible: Vec<PlayerId>
+}
+
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug)]
+pub enum Street {
+    Preflop,
+    Flop,
+    Turn,
+    River
+}
+
+impl std::fmt::Display for Street {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Street::Preflop => "Preflop",
+            Street::Flop    => "Flop",
+            Street::Turn    => "Turn",
+            Street::River   => "River"
+        }.fmt(f)
+    }
+}
+
+// The street being played with 'community' on the board.
+pub fn street_of(community: &[Card]) -> Result<Street, DealError> {
+    match community.len() {
+        0 => Ok(Street::Preflop),
+        3 => Ok(Street::Flop),
+        4 => Ok(Street::Turn),
+        5 => Ok(Street::River),
+        n => Err(DealError::BadBoardSize(n))
+    }
+}
+
+pub struct Round {
+    pub deck: Deck,
+    pub community_cards: Vec<Card>,
+    pub main_pot: Pot,
+    pub side_pots: Vec<Pot>,
+    // In seat order, starting with the dealer.
+    pub players: Vec<Player>,
+    // (small blind, big blind)
+    pub blinds: (u32, u32),
+    // Chips each player has bet on the current street, which 'collect_bets'
+    // sweeps into the pots.
+    pub street_contributions: BTreeMap<PlayerId, u32>,
+    // Chips each player has put into the pots on earlier streets.
+    pub contributions: BTreeMap<PlayerId, u32>,
+    // In a bomb pot the betting starts on the flop.
+    pub skip_preflop: bool,
+    // Blinds owed by players returning from sitting out, posted along with
+    // the regular blinds, e.g. those returned by 'Tournament::start_round'.
+    pub postings: Vec<BlindPosting>
+}
+
+// What a player is shown when it is their turn to act.
+pub struct GameState<'a> {
+    pub street: Street,
+    pub player: PlayerId,
+    pub hole_cards: &'a [Card],
+    pub community_cards: &'a [Card],
+    pub betting: &'a BettingRound,
+    // All chips put in so far, across all streets.
+    pub pot: u32
+}
+
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct RoundResult {
+    // Chips won by each player, in the order the pots were awarded.
+    pub winnings: Vec<(PlayerId, u32)>,
+    // Whether hands were compared, rather than everyone else folding.
+    pub showdown: bool,
+    // The pots split between players tied at showdown.
+    pub chops: Vec<Chop>
+}
+
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct Chop {
+    pub pot: u32,
+    // Each winner's share, from the left of the dealer. Chips that do not
+    // split evenly go one each to the first winners.
+    pub shares: Vec<(PlayerId, u32)>
+}
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum DealError {
+    // The deck ran out of cards.
+    DeckExhausted,
+    // The streets must be dealt in order: flop, turn, river.
+    OutOfOrder,
+    // The card was dealt more than once.
+    DuplicateCard(Card),
+    // The board must hold 0, 3, 4 or 5 cards.
+    BadBoardSize(usize)
+}
+
+impl std::fmt::Display for DealError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use DealError::*;
+
+        match self {
+            DeckExhausted       => "the deck ran out of cards".fmt(f),
+            OutOfOrder          => "community cards dealt out of order".fmt(f),
+            DuplicateCard(card) => write!(f, "card {} was dealt more than once", card),
+            BadBoardSize(n)     => write!(f, "a board cannot have {} cards", n)
+        }
+    }
+}
+
+impl std::error::Error for DealError {}
+
+impl Round {
+    pub fn new(deck: Deck) -> Round {
+        Round::with_players(deck, Vec::new(), (0, 0))
+    }
+
+    pub fn with_players(deck: Deck, players: Vec<Player>, blinds: (u32, u32)) -> Round {
+        Round {
+            deck,
+            community_cards: Vec::with_capacity(5),
+            main_pot: Pot { size: 0, eligible: Vec::new() },
+            side_pots: Vec::new(),
+            players,
+            blinds,
+            street_contributions: BTreeMap::new(),
+            contributions: BTreeMap::new(),
+            skip_preflop: false,
+            postings: Vec::new()
+        }
+    }
+
+    // A bomb pot dealt from 'deck': every active player puts in 'ante', and
+    // there are neither blinds nor preflop betting. The players' money goes
+    // down by the ante; they are then seated with 'Round::players' to play
+    // the hand.
+    pub fn new_bomb(deck: Deck, ante: u32, players: &mut [Player]) -> Round {
+        let mut round = Round::new(deck);
+
+        for player in players.iter_mut() {
+            if let Ok(paid) = player.bet(ante) {
+                round.record_bet(player.id, paid);
+            }
+        }
+
+        round.collect_bets();
+        round.skip_preflop = true;
+        round
+    }
+
+    // Records a bet made by 'id' on the current street.
+    pub fn record_bet(&mut self, id: PlayerId, amount: u32) {
+        *self.street_contributions.entry(id).or_default() += amount;
+    }
+
+    // Ends the street: its bets are added to each player's contributions and
+    // the main and side pots are built again from them. The pots can be won
+    // by the players who have not folded or, without any players, by anyone
+    // who contributed.
+    pub fn collect_bets(&mut self) {
+        for (id, amount) in std::mem::take(&mut self.street_contributions) {
+            *self.contributions.entry(id).or_default() += amount;
+        }
+
+        let contributions: Vec<(PlayerId, u32)> = self.contributions.iter()
+            .map(|(&id, &amount)| (id, amount))
+            .collect();
+
+        let eligible: Vec<PlayerId> = if self.players.is_empty() {
+            self.contributions.keys().copied().collect()
+        }
+        else {
+            self.players.iter()
+                .filter(|player| player.status != PlayerStatus::Folded)
+                .map(|player| player.id)
+                .collect()
+        };
+
+        let mut pots = build_pots(&contributions, &eligible).into_iter();
+
+        self.main_pot = pots.next().unwrap_or(Pot { size: 0, eligible: Vec::new() });
+        self.side_pots = pots.collect();
+    }
+
+    // Plays out a whole hand: deals the hole cards, posts the blinds, runs the
+    // betting on every street and awards the pots. 'actions' decides for the
+    // player whose turn it is; an illegal action counts as a fold. Players
+    // who have folded already, or have no chips and none in the pot, sit the
+    // hand out: they are neither dealt in nor seated at the betting.
+    pub fn play(&mut self, actions: impl FnMut(&GameState) -> Action) -> RoundResult {
+        let (playing, sitting_out): (Vec<_>, Vec<_>) = std::mem::take(&mut self.players)
+            .into_iter()
+            .enumerate()
+            .partition(|(_, player)| {
+                let in_pot = self.contributions.contains_key(&player.id)
+                    || self.street_contributions.contains_key(&player.id);
+
+                player.status != PlayerStatus::Folded && (player.money > 0 || in_pot)
+            });
+
+        self.players = playing.into_iter().map(|(_, player)| player).collect();
+
+        let result = self.play_seated(actions);
+
+        for (seat, mut player) in sitting_out {
+            player.hole_cards.clear();
+            self.players.insert(seat, player);
+        }
+
+        result
+    }
+
+    fn play_seated(&mut self, mut actions: impl FnMut(&GameState) -> Action) -> RoundResult {
+        let n = self.players.len();
+        assert!(n >= 2, "a round needs at least two players");
+
+        let mut players = std::mem::take(&mut self.players);
+        self.deal_hole_cards(&mut players).expect("the deck ran out of hole cards");
+        self.players = players;
+
+        // Heads-up, the dealer posts the small blind.
+        let (small_blind, big_blind) = if n == 2 { (0, 1) } else { (1, 2) };
+        let (small, big) = self.blinds;
+
+        let stacks: Vec<u32> = self.players.iter().map(|player| player.money).collect();
+
+        let (streets, mut betting) = if self.skip_preflop {
+            // The antes of a bomb pot are already in.
+            let betting = BettingRound::new(&stacks, 1 % n, big);
+
+            (&[Street::Flop, Street::Turn, Street::River][..], betting)
+        }
+        else {
+            let mut betting = BettingRound::new(&stacks, (big_blind + 1) % n, big);
+            betting.post_blind(small_blind, small);
+            betting.post_blind(big_blind, big);
+
+            for posting in &self.postings {
+                if let Some(seat) = self.players.iter().position(|player| player.id == posting.player) {
+                    betting.post_dead_blind(seat, posting.dead);
+                    betting.post_blind(seat, posting.live);
+                }
+            }
+
+            self.street_contributions.clear();
+            self.contributions.clear();
+
+            (&[Street::Preflop, Street::Flop, Street::Turn, Street::River][..], betting)
+        };
+
+        for &street in streets {
+            if street != Street::Preflop {
+                match street {
+                    Street::Flop => self.deal_flop(),
+                    Street::Turn => self.deal_turn(),
+                    _            => self.deal_river()
+                }.expect("the deck ran out of community cards");
+
+                if street != streets[0] {
+                    betting = betting.next_street(1 % n);
+                }
+            }
+
+            while !betting.is_complete() {
+                let turn = betting.turn();
+                let seats = betting.seats();
+
+                // Nobody bets against players who are all-in.
+                let can_act = seats.iter().filter(|seat| seat.status == PlayerStatus::Active).count();
+
+                if can_act < 2 && seats[turn].bet >= betting.current_bet() {
+                    break;
+                }
+
+                let state = GameState {
+                    street,
+                    player: self.players[turn].id,
+                    hole_cards: &self.players[turn].hole_cards,
+                    community_cards: &self.community_cards,
+                    betting: &betting,
+                    pot: self.contributions.values().sum::<u32>() + betting.pot()
+                };
+
+                let action = actions(&state);
+
+                if betting.apply(action).is_err() {
+                    betting.apply(Action::Fold).unwrap();
+                }
+            }
+
+            for (player, seat) in self.players.iter_mut().zip(betting.seats()) {
+                player.money = seat.stack;
+                player.status = seat.status;
+                *self.street_contributions.entry(player.id).or_default() += seat.ante + seat.bet;
+            }
+
+            self.collect_bets();
+
+            let in_hand = self.players.iter().filter(|player| player.status != PlayerStatus::Folded).count();
+
+            if in_hand == 1 {
+                break;
+            }
+        }
+
+        self.award_pots()
+    }
+
+    // Awards the main and side pots to the best hands among the players who
+    // have not folded and credits the winners, as at the end of 'play'.
+    pub fn award_pots(&mut self) -> RoundResult {
+        let n = self.players.len();
+
+        let eligible: Vec<PlayerId> = self.players.iter()
+            .filter(|player| player.status != PlayerStatus::Folded)
+            .map(|player| player.id)
+            .collect();
+
+        let showdown = eligible.len() > 1;
+
+        let hands: Vec<(PlayerId, Hand)> = if showdown {
+            self.players.iter()
+                .filter(|player| eligible.contains(&player.id))
+                .map(|player| (player.id, form_best_hand(&self.community_cards, &player.hole_cards).unwrap()))
+                .collect()
+        }
+        else {
+            Vec::new()
+        };
+
+        let seat_of = |id: PlayerId| self.players.iter().position(|player| player.id == id).unwrap();
+
+        let mut winnings: Vec<(PlayerId, u32)> = Vec::new();
+        let mut chops = Vec::new();
+
+        for pot in std::iter::once(&self.main_pot).chain(&self.side_pots) {
+            let contesting: Vec<(PlayerId, Hand)> = hands.iter()
+                .filter(|(id, _)| pot.eligible.contains(id))
+                .cloned()
+                .collect();
+
+            let mut winners = match contesting.len() {
+                0 => pot.eligible.clone(),
+                _ => showdown_winners(&contesting)
+            };
+
+            // Odd chips go to the players closest to the left of the dealer.
+            winners.sort_by_key(|&id| (seat_of(id) + n - 1) % n);
+
+            let winners: Vec<&Player> = winners.iter()
+                .map(|&id| &self.players[seat_of(id)])
+                .collect();
+
+            let shares = distribute(pot, &winners);
+
+            for &(id, amount) in &shares {
+                match winnings.iter_mut().find(|(winner, _)| *winner == id) {
+                    Some((_, total)) => *total += amount,
+                    None             => winnings.push((id, amount))
+                }
+            }
+
+            if contesting.len() > 1 && shares.len() > 1 {
+                chops.push(Chop { pot: pot.size, shares });
+            }
+        }
+
+        for player in &mut self.players {
+            if let Some((_, amount)) = winnings.iter().find(|(id, _)| *id == player.id) {
+                player.money += amount;
+            }
+        }
+
+        RoundResult { winnings, showdown, chops }
+    }
+
+    // Deals two hole cards to every player who has not folded, which includes
+    // anyone all-in for an ante, one card at a time starting left of the
+    // dealer. 'players' are in seat order starting with the dealer. Nothing
+    // is dealt if the deck cannot go round twice.
+    pub fn deal_hole_cards(&mut self, players: &mut [Player]) -> Result<(), DealError> {
+        let n = players.len();
+
+        for player in players.iter_mut() {
+            player.hole_cards.clear();
+        }
+
+        let active: Vec<usize> = (1..=n)
+            .map(|offset| offset % n)
+            .filter(|&seat| players[seat].status != PlayerStatus::Folded)
+            .collect();
+
+        let cards = self.deck.draw_n(2 * active.len()).ok_or(DealError::DeckExhausted)?;
+
+        for (&seat, &card) in active.iter().cycle().zip(&cards) {
+            players[seat].hole_cards.push(card);
+        }
+
+        Ok(())
+    }
+
+    pub fn deal_flop(&mut self) -> Result<(), DealError> {
+        self.burn_and_deal(Street::Preflop, 3)
+    }
+
+    pub fn deal_turn(&mut self) -> Result<(), DealError> {
+        self.burn_and_deal(Street::Flop, 1)
+    }
+
+    pub fn deal_river(&mut self) -> Result<(), DealError> {
+        self.burn_and_deal(Street::Turn, 1)
+    }
+
+    // Burns one card and then deals 'n' cards to the board, provided the
+    // board currently belongs to the 'expected' street.
+    fn burn_and_deal(&mut self, expected: Street, n: usize) -> Result<(), DealError> {
+        if street_of(&self.community_cards)? != expected {
+            return Err(DealError::OutOfOrder);
+        }
+
+        let cards = self.deck.draw_n(n + 1).ok_or(DealError::DeckExhausted)?;
+
+        self.community_cards.extend_from_slice(&cards[1..]);
+        Ok(())
+    }
+}
+
+// Checks that no card is both on the board and in a player's hand, or in the
+// hands of two players.
+pub fn validate_deal(community: &[Card], hole_sets: &[&[Card]]) -> Result<(), DealError> {
+    let mut seen: Vec<Card> = Vec::with_capacity(community.len() + 2 * hole_sets.len());
+
+    for &card in community.iter().chain(hole_sets.iter().copied().flatten()) {
+        if seen.contains(&card) {
+            return Err(DealError::DuplicateCard(card));
+        }
+
+        seen.push(card);
+    }
+
+    Ok(())
+}
+
+// Splits the players' total contributions into the main pot followed by the
+// side pots. 'eligible' lists the players still contesting the hand; chips
+// put in by players who have folded are still part of the pots.
+pub fn build_pots(contributions: &[(PlayerId, u32)], eligible: &[PlayerId]) -> Vec<Pot> {
+    let is_eligible = |id: &PlayerId| eligible.contains(id);
+
+    // Every distinct all-in amount of a contesting player caps a pot.
+    let levels: Vec<u32> = contributions.iter()
+        .filter(|(id, _)| is_eligible(id))
+        .map(|&(_, amount)| amount)
+        .filter(|&amount| amount > 0)
+        .sorted()
+        .dedup()
+        .collect();
+
+    let mut pots: Vec<Pot> = Vec::with_capacity(levels.len());
+    let mut floor = 0;
+
+    for level in levels {
+        let size = contributions.iter()
+            .map(|&(_, amount)| amount.clamp(floor, level) - floor)
+            .sum();
+
+        let players = contributions.iter()
+            .filter(|(id, amount)| is_eligible(id) && *amount >= level)
+            .map(|&(id, _)| id)
+            .collect();
+
+        pots.push(Pot { size, eligible: players });
+        floor = level;
+    }
+
+    // Folded players may have put in more than anyone still contesting.
+    let excess: u32 = contributions.iter()
+        .map(|&(_, amount)| amount.saturating_sub(floor))
+        .sum();
+
+    if let Some(last) = pots.last_mut() {
+        last.size += excess;
+    }
+
+    pots
+}
+
+// Splits a pot evenly among tied winners. 'ranked_winners' must be ordered by
+// seat starting from the player left of the dealer button, since any
+// indivisible chips go one at a time to the players closest to that side.
+pub fn distribute(pot: &Pot, ranked_winners: &[&Player]) -> Vec<(PlayerId, u32)> {
+    if ranked_winners.is_empty() {
+        return Vec::new();
+    }
+
+    let n = ranked_winners.len() as u32;
+    let share = pot.size / n;
+    let odd_chips = (pot.size % n) as usize;
+
+    ranked_winners.iter()
+        .enumerate()
+        .map(|(i, player)| (player.id, share + (i < odd_chips) as u32))
+        .collect()
+}
+
+// Every player tied for the best hand at showdown, in the order given.
+pub fn showdown_winners(hands: &[(PlayerId, Hand)]) -> Vec<PlayerId> {
+    let best = match hands.iter().map(|(_, hand)| hand).max() {
+        Some(best) => best,
+        None => return Vec::new()
+    };
+
+    hands.iter()
+        .filter(|(_, hand)| hand == best)
+        .map(|&(id, _)| id)
+        .collect()
+}
+
+#[derive(PartialEq, Eq, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BlindSchedule {
+    // (small blind, big blind, ante) for each level, in the order they are played.
+    pub levels: Vec<(u32, u32, u32)>,
+    pub level_duration: std::time::Duration
+}
+
+impl BlindSchedule {
+    pub fn new(levels: Vec<(u32, u32, u32)>, level_duration: std::time::Duration) -> BlindSchedule {
+        assert!(!levels.is_empty(), "a blind schedule needs at least one level");
+
+        BlindSchedule { levels, level_duration }
+    }
+}
+
+pub struct Tournament {
+    pub blinds: (u32, u32),
+    pub players: Vec<Player>,
+    pub dealer: u8,
+    pub schedule: BlindSchedule,
+    // Index into 'schedule.levels'.
+    pub level: usize,
+    pub deck: Deck,
+    // Players who sat out while the blinds passed them.
+    pub missed_blinds: Vec<PlayerId>,
+    // Players back from sitting out, who owe their blinds in the next hand.
+    pub returning: Vec<PlayerId>,
+    // Shuffles every deck of the tournament.
+    rng: StdRng,
+    // The seed 'rng' started from, if any, and the decks it has shuffled
+    // since. Together they are enough to rebuild it.
+    seed: Option<u64>,
+    shuffles: u64
+}
+
+// The blinds owed by a player returning from sitting out.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct BlindPosting {
+    pub player: PlayerId,
+    // The missed small blind goes straight into the pot and is not a bet.
+    pub dead: u32,
+    // The big blind is a live bet, as if posted in turn.
+    pub live: u32
+}
+
+// The next hand as prepared by 'Tournament::start_round'.
+#[must_use]
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct RoundStart {
+    // The seats of the small and big blind.
+    pub blinds: (usize, usize),
+    // Blinds owed by returning players, to be posted with 'Round::postings'.
+    pub postings: Vec<BlindPosting>
+}
+
+#[derive(PartialEq, Eq, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PlayerSnapshot {
+    pub id: PlayerId,
+    pub name: String,
+    pub money: u32,
+    pub status: PlayerStatus
+}
+
+// Everything needed to resume a tournament between hands. Hole cards are not
+// kept, since a snapshot is not taken in the middle of a hand.
+#[derive(PartialEq, Eq, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GameSnapshot {
+    pub players: Vec<PlayerSnapshot>,
+    pub schedule: BlindSchedule,
+    pub level: usize,
+    pub dealer: u8,
+    // The cards left in the deck, the next one to be drawn last.
+    pub deck: Vec<Card>,
+    pub missed_blinds: Vec<PlayerId>,
+    pub returning: Vec<PlayerId>,
+    // The seed of a seeded tournament and how many decks it has shuffled, so
+    // that a restored tournament goes on to deal the same cards.
+    pub seed: Option<u64>,
+    pub shuffles: u64
+}
+
+// The generator of a tournament seeded with 'seed', or by entropy if there is
+// none, after it has shuffled 'shuffles' decks.
+fn tournament_rng(seed: Option<u64>, shuffles: u64) -> StdRng {
+    let mut rng = match seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None       => return StdRng::from_entropy()
+    };
+
+    let mut deck = Deck::new();
+
+    for _ in 0..shuffles {
+        deck.shuffle_with_rng(&mut rng);
+    }
+
+    rng
+}
+
+impl Tournament {
+    pub fn new(players: Vec<Player>, schedule: BlindSchedule) -> Tournament {
+        Tournament::with_seed(players, schedule, None)
+    }
+
+    fn with_seed(players: Vec<Player>, schedule: BlindSchedule, seed: Option<u64>) -> Tournament {
+        let (small, big, _) = schedule.levels[0];
+
+        let mut tournament = Tournament {
+            blinds: (small, big),
+            players,
+            dealer: 0,
+            schedule,
+            level: 0,
+            deck: Deck::new(),
+            missed_blinds: Vec::new(),
+            returning: Vec::new(),
+            rng: tournament_rng(seed, 0),
+            seed,
+            shuffles: 0
+        };
+
+        tournament.shuffle_deck();
+        tournament
+    }
+
+    // A tournament with a single blind level whose decks are all shuffled by
+    // a generator seeded with 'seed', so that the same inputs always deal the
+    // same cards. Players get ids in the order of 'names'.
+    pub fn new_seeded(names: Vec<String>, starting_stack: u32, blinds: (u32, u32), seed: u64) -> Tournament {
+        let players = names.iter()
+            .enumerate()
+            .map(|(i, name)| Player::new(PlayerId(i as u32), name, starting_stack))
+            .collect();
+
+        let schedule = BlindSchedule::new(vec![(blinds.0, blinds.1, 0)], std::time::Duration::MAX);
+
+        Tournament::with_seed(players, schedule, Some(seed))
+    }
+
+    pub fn snapshot(&self) -> GameSnapshot {
+        let players = self.players.iter()
+            .map(|player| PlayerSnapshot {
+                id: player.id,
+                name: player.name.clone(),
+                money: player.money,
+                status: player.status
+            })
+            .collect();
+
+        GameSnapshot {
+            players,
+            schedule: self.schedule.clone(),
+            level: self.level,
+            dealer: self.dealer,
+            deck: self.deck.cards().to_vec(),
+            missed_blinds: self.missed_blinds.clone(),
+            returning: self.returning.clone(),
+            seed: self.seed,
+            shuffles: self.shuffles
+        }
+    }
+
+    pub fn restore(snapshot: GameSnapshot) -> Tournament {
+        let players = snapshot.players.into_iter()
+            .map(|saved| Player {
+                status: saved.status,
+                ..Player::new(saved.id, &saved.name, saved.money)
+            })
+            .collect();
+
+        let (small, big, _) = snapshot.schedule.levels[snapshot.level];
+
+        Tournament {
+            blinds: (small, big),
+            players,
+            dealer: snapshot.dealer,
+            schedule: snapshot.schedule,
+            level: snapshot.level,
+            deck: Deck::from_cards(snapshot.deck),
+            missed_blinds: snapshot.missed_blinds,
+            returning: snapshot.returning,
+            rng: tournament_rng(snapshot.seed, snapshot.shuffles),
+            seed: snapshot.seed,
+            shuffles: snapshot.shuffles
+        }
+    }
+
+    // Refills the deck and shuffles it for the next hand.
+    pub fn shuffle_deck(&mut self) {
+        self.deck.reset();
+        self.deck.shuffle_with_rng(&mut self.rng);
+        self.shuffles += 1;
+    }
+
+    // Moves on to the next blind level. The last level lasts indefinitely.
+    pub fn advance_level(&mut self) {
+        self.level = (self.level + 1).min(self.schedule.levels.len() - 1);
+
+        let (small, big, _) = self.schedule.levels[self.level];
+        self.blinds = (small, big);
+    }
+
+    pub fn current_ante(&self) -> u32 {
+        self.schedule.levels[self.level].2
+    }
+
+    pub fn player(&self, id: PlayerId) -> Option<&Player> {
+        self.players.iter().find(|player| player.id == id)
+    }
+
+    // A single line with every player's stack, biggest first, e.g.
+    // "Bob 2400, Alice 600, Carol OUT". Busted players come last.
+    pub fn scoreboard(&self) -> String {
+        self.players.iter()
+            .sorted_by_key(|player| (player.is_busted(), std::cmp::Reverse(player.money)))
+            .map(|player| match player.is_busted() {
+                true  => format!("{} OUT", player.name),
+                false => format!("{} {}", player.name, player.money)
+            })
+            .join(", ")
+    }
+
+    // Records that a player sitting out has let the blinds pass them.
+    pub fn miss_blinds(&mut self, id: PlayerId) {
+        if !self.missed_blinds.contains(&id) {
+            self.missed_blinds.push(id);
+        }
+    }
+
+    // Deals a player who missed the blinds back in. They owe a dead small
+    // blind and a live big blind, which the next 'start_round' charges.
+    // Returns whether the player had missed the blinds.
+    pub fn return_player(&mut self, id: PlayerId) -> bool {
+        let i = match self.missed_blinds.iter().position(|&missed| missed == id) {
+            Some(i) => i,
+            None => return false
+        };
+
+        self.missed_blinds.remove(i);
+        self.returning.push(id);
+        true
+    }
+
+    // Prepares the next hand: busted players leave the table and the button
+    // moves to the next remaining player. Returns the seats of the blinds and
+    // what returning players owe, or nothing if fewer than two players are
+    // left. A returning player in one of the blinds owes nothing more.
+    pub fn start_round(&mut self) -> Option<RoundStart> {
+        let n = self.players.len();
+
+        let next_dealer = (1..=n)
+            .map(|offset| &self.players[(self.dealer as usize + offset) % n])
+            .find(|player| !player.is_busted())
+            .map(|player| player.id);
+
+        self.players.retain(|player| !player.is_busted());
+
+        let players = &self.players;
+        self.missed_blinds.retain(|&id| players.iter().any(|player| player.id == id));
+        self.returning.retain(|&id| players.iter().any(|player| player.id == id));
+
+        let dealer = self.players.iter()
+            .position(|player| Some(player.id) == next_dealer)?;
+
+        self.dealer = dealer as u8;
+
+        let blinds = match self.players.len() {
+            0 | 1 => return None,
+            // Heads-up, the dealer posts the small blind.
+            2 => (dealer, (dealer + 1) % 2),
+            n => ((dealer + 1) % n, (dealer + 2) % n)
+        };
+
+        let (small, big) = self.blinds;
+
+        let postings = std::mem::take(&mut self.returning)
+            .into_iter()
+            .filter(|&id| {
+                let seat = self.players.iter().position(|player| player.id == id);
+                seat != Some(blinds.0) && seat != Some(blinds.1)
+            })
+            .map(|player| BlindPosting { player, dead: small, live: big })
+            .collect();
+
+        Some(RoundStart { blinds, postings })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn community_card_streets() {
+        let mut round = Round::new(Deck::generate());
+
+        assert_eq!(round.deal_turn(), Err(DealError::OutOfOrder));
+        assert_eq!(round.deal_river(), Err(DealError::OutOfOrder));
+
+        round.deal_flop().unwrap();
+        assert_eq!(round.community_cards.len(), 3);
+        assert_eq!(round.deck.remaining(), 48);
+
+        assert_eq!(round.deal_flop(), Err(DealError::OutOfOrder));
+        assert_eq!(round.deal_river(), Err(DealError::OutOfOrder));
+
+        round.deal_turn().unwrap();
+        assert_eq!(round.community_cards.len(), 4);
+        assert_eq!(round.deck.remaining(), 46);
+
+        round.deal_river().unwrap();
+        assert_eq!(round.community_cards.len(), 5);
+        assert_eq!(round.deck.remaining(), 44);
+
+        assert_eq!(round.deal_river(), Err(DealError::OutOfOrder));
+    }
+
+    #[test]
+    fn hole_cards_are_dealt_in_rotation() {
+        let mut round = Round::new(Deck::shuffled_from_seed(92));
+        let mut players = players(&[1000, 1000, 1000, 1000]);
+
+        players[2].hole_cards = vec![Card::from_index(0).unwrap()];
+        players[3].fold();
+
+        let dealt = Deck::shuffled_from_seed(92).draw_n(6).unwrap();
+
+        round.deal_hole_cards(&mut players).unwrap();
+
+        // Seat 1 is left of the dealer; the folded seat 3 is skipped.
+        assert_eq!(players[1].hole_cards, [dealt[0], dealt[3]]);
+        assert_eq!(players[2].hole_cards, [dealt[1], dealt[4]]);
+        assert_eq!(players[0].hole_cards, [dealt[2], dealt[5]]);
+        assert!(players[3].hole_cards.is_empty());
+        assert_eq!(round.deck.remaining(), 46);
+
+        round.deck.draw_n(41).unwrap();
+
+        assert_eq!(round.deal_hole_cards(&mut players), Err(DealError::DeckExhausted));
+        assert_eq!(round.deck.remaining(), 5);
+    }
+
+    #[test]
+    fn dealing_from_exhausted_deck() {
+        let mut round = Round::new(Deck::generate());
+
+        round.deck.draw_n(50).unwrap();
+
+        assert_eq!(round.deal_flop(), Err(DealError::DeckExhausted));
+        assert!(round.community_cards.is_empty());
+        assert_eq!(round.deck.remaining(), 2);
+    }
+
+    fn players(stacks: &[u32]) -> Vec<Player> {
+        stacks.iter()
+            .enumerate()
+            .map(|(i, &money)| Player::new(PlayerId(i as u32), &format!("p{}", i), money))
+            .collect()
+    }
+
+    #[test]
+    fn everyone_folds_to_the_big_blind() {
+        let mut round = Round::with_players(Deck::shuffled_from_seed(7), players(&[1000, 1000, 1000]), (10, 20));
+
+        let mut seen = Vec::new();
+
+        let result = round.play(|state| {
+            seen.push((state.player, state.pot));
+            Action::Fold
+        });
+
+        // The dealer acts first preflop, then the small blind folds too.
+        assert_eq!(seen, [(PlayerId(0), 30), (PlayerId(1), 30)]);
+        assert_eq!(result, RoundResult { winnings: vec![(PlayerId(2), 30)], showdown: false, chops: vec![] });
+
+        assert!(round.community_cards.is_empty());
+        assert_eq!(round.players.iter().map(|player| player.money).collect::<Vec<_>>(), [1000, 990, 1010]);
+        assert_eq!(round.main_pot, Pot { size: 30, eligible: vec![PlayerId(2)] });
+    }
+
+    #[test]
+    fn bets_are_collected_each_street() {
+        let mut round = Round::with_players(Deck::generate(), players(&[1000, 60, 1000]), (10, 20));
+
+        let stacks: Vec<u32> = round.players.iter().map(|player| player.money).collect();
+        let mut betting = BettingRound::new(&stacks, 0, 20);
+
+        betting.apply(Action::Raise(100)).unwrap();
+        betting.apply(Action::AllIn).unwrap();
+        betting.apply(Action::Call).unwrap();
+
+        for (i, seat) in betting.seats().iter().enumerate() {
+            round.record_bet(PlayerId(i as u32), seat.bet);
+        }
+
+        round.collect_bets();
+
+        assert!(round.street_contributions.is_empty());
+        assert_eq!(round.contributions.values().sum::<u32>(), 260);
+        assert_eq!(round.main_pot, Pot { size: 180, eligible: vec![PlayerId(0), PlayerId(1), PlayerId(2)] });
+        assert_eq!(round.side_pots, [Pot { size: 80, eligible: vec![PlayerId(0), PlayerId(2)] }]);
+
+        // A bet on the next street only adds to the side pot.
+        round.record_bet(PlayerId(0), 50);
+        round.record_bet(PlayerId(2), 50);
+        round.collect_bets();
+
+        assert_eq!(round.contributions[&PlayerId(2)], 150);
+        assert_eq!(round.main_pot.size + round.side_pots[0].size, 360);
+    }
+
+    #[test]
+    fn bomb_pot_betting_starts_on_the_flop() {
+        let mut players = players(&[1000, 1000, 30]);
+        let mut round = Round::new_bomb(Deck::shuffled_from_seed(95), 50, &mut players);
+
+        assert_eq!(players.iter().map(|player| player.money).collect::<Vec<_>>(), [950, 950, 0]);
+        assert_eq!(round.contributions.values().copied().collect::<Vec<_>>(), [50, 50, 30]);
+        assert_eq!(round.main_pot.size + round.side_pots.iter().map(|pot| pot.size).sum::<u32>(), 130);
+
+        round.players = players;
+        round.blinds = (10, 20);
+
+        let mut streets = Vec::new();
+
+        let result = round.play(|state| {
+            streets.push((state.street, state.community_cards.len(), state.pot));
+            Action::Check
+        });
+
+        // Only the two players with chips left get to act.
+        assert_eq!(streets[0], (Street::Flop, 3, 130));
+        assert_eq!(streets.len(), 6);
+        assert!(result.showdown);
+        assert_eq!(round.players[2].hole_cards.len(), 2);
+        assert_eq!(round.players.iter().map(|player| player.money).sum::<u32>(), 2030);
+    }
+
+    #[test]
+    fn checked_down_to_showdown() {
+        let mut round = Round::with_players(Deck::shuffled_from_seed(7), players(&[1000, 500, 1000, 1000]), (10, 20));
+
+        let result = round.play(|state| {
+            if state.betting.current_bet() > 0 { Action::Call } else { Action::Check }
+        });
+
+        assert!(result.showdown);
+        assert_eq!(round.community_cards.len(), 5);
+        assert_eq!(round.main_pot.size, 80);
+        assert_eq!(result.winnings.iter().map(|&(_, amount)| amount).sum::<u32>(), 80);
+        assert_eq!(round.players.iter().map(|player| player.money).sum::<u32>(), 3500);
+    }
+
+    #[test]
+    fn all_in_side_pot() {
+        let mut round = Round::with_players(Deck::shuffled_from_seed(7), players(&[1000, 100, 1000]), (10, 20));
+
+        let result = round.play(|state| {
+            if state.street == Street::Preflop { Action::AllIn } else { Action::Check }
+        });
+
+        assert!(result.showdown);
+        assert_eq!(round.community_cards.len(), 5);
+        assert_eq!(round.main_pot, Pot { size: 300, eligible: vec![PlayerId(0), PlayerId(1), PlayerId(2)] });
+        assert_eq!(round.side_pots, [Pot { size: 1800, eligible: vec![PlayerId(0), PlayerId(2)] }]);
+        assert_eq!(round.players.iter().map(|player| player.money).sum::<u32>(), 2100);
+    }
+
+    #[test]
+    fn chopped_pot_shares() {
+        use crate::card::parse_cards;
+
+        let mut round = Round::with_players(Deck::new(), players(&[975, 975, 999]), (0, 0));
+
+        round.community_cards = parse_cards("As Ks Qs Js Ts").unwrap();
+        round.players[0].hole_cards = parse_cards("2c 3d").unwrap();
+        round.players[1].hole_cards = parse_cards("2d 3c").unwrap();
+        round.players[2].fold();
+
+        round.record_bet(PlayerId(0), 25);
+        round.record_bet(PlayerId(1), 25);
+        round.record_bet(PlayerId(2), 1);
+        round.collect_bets();
+
+        // Both play the board. The odd chip goes to the left of the dealer.
+        let result = round.award_pots();
+
+        assert_eq!(result.chops, [Chop { pot: 51, shares: vec![(PlayerId(1), 26), (PlayerId(0), 25)] }]);
+        assert_eq!(round.players[1].money, 1001);
+    }
+
+    #[test]
+    fn busted_players_sit_out() {
+        let mut round = Round::with_players(Deck::shuffled_from_seed(72), players(&[1000, 0, 1000, 1000]), (10, 20));
+
+        round.players[3].fold();
+
+        let result = round.play(|state| {
+            assert!(state.player != PlayerId(1) && state.player != PlayerId(3));
+            if state.betting.current_bet() > 0 { Action::Call } else { Action::Check }
+        });
+
+        // Heads-up between seats 0 and 2, who limp and check it down.
+        assert!(result.showdown);
+        assert_eq!(round.players.iter().map(|player| player.id.0).collect::<Vec<_>>(), [0, 1, 2, 3]);
+        assert!(round.players[1].hole_cards.is_empty() && round.players[3].hole_cards.is_empty());
+        assert!(result.winnings.iter().all(|&(id, _)| id == PlayerId(0) || id == PlayerId(2)));
+        assert_eq!(result.winnings.iter().map(|&(_, won)| won).sum::<u32>(), 40);
+        assert_eq!(round.players[1].money + round.players[3].money, 1000);
+    }
+
+    #[test]
+    fn streets_from_the_board() {
+        let board = Deck::new().draw_n(6).unwrap();
+
+        assert_eq!(street_of(&board[..0]), Ok(Street::Preflop));
+        assert_eq!(street_of(&board[..3]), Ok(Street::Flop));
+        assert_eq!(street_of(&board[..4]), Ok(Street::Turn));
+        assert_eq!(street_of(&board[..5]), Ok(Street::River));
+
+        for n in [1, 2, 6] {
+            assert_eq!(street_of(&board[..n]), Err(DealError::BadBoardSize(n)));
+        }
+    }
+
+    #[test]
+    fn duplicate_cards_in_a_deal() {
+        use crate::card::parse_cards;
+
+        let board = parse_cards("Kh 7s 2d").unwrap();
+        let a = parse_cards("As Ad").unwrap();
+        let b = parse_cards("Qc As").unwrap();
+        let c = parse_cards("Qd Jd").unwrap();
+
+        let ace = Card { suit: crate::card::CardSuit::Spades, rank: crate::card::CardRank::Ace };
+
+        assert_eq!(validate_deal(&board, &[&a, &c]), Ok(()));
+        assert_eq!(validate_deal(&board, &[&a, &b, &c]), Err(DealError::DuplicateCard(ace)));
+        assert_eq!(validate_deal(&a, &[&b]), Err(DealError::DuplicateCard(ace)));
+
+        assert_eq!(
+            DealError::DuplicateCard(ace).to_string(),
+            format!("card {} was dealt more than once", ace)
+        );
+    }
+
+    #[test]
+    fn three_way_all_in_pots() {
+        let (a, b, c) = (PlayerId(0), PlayerId(1), PlayerId(2));
+
+        let pots = build_pots(&[(a, 100), (b, 50), (c, 200)], &[a, b, c]);
+
+        assert_eq!(pots, vec![
+            Pot { size: 150, eligible: vec![a, b, c] },
+            Pot { size: 100, eligible: vec![a, c] },
+            Pot { size: 100, eligible: vec![c] }
+        ]);
+    }
+
+    #[test]
+    fn folded_player_contributes_to_pots() {
+        let (a, b, c) = (PlayerId(0), PlayerId(1), PlayerId(2));
+
+        // 'c' called 80 and then folded to a later bet.
+        let pots = build_pots(&[(a, 50), (b, 150), (c, 80)], &[a, b]);
+
+        assert_eq!(pots, vec![
+            Pot { size: 150, eligible: vec![a, b] },
+            Pot { size: 130, eligible: vec![b] }
+        ]);
+
+        assert_eq!(pots.iter().map(|pot| pot.size).sum::<u32>(), 280);
+    }
+
+    #[test]
+    fn tied_showdown() {
+        let hand = |codes: &str| codes.parse::<Hand>().unwrap();
+
+        let hands = [
+            (PlayerId(0), hand("Ah Kd 9c 9s 2h")),
+            (PlayerId(1), hand("8h 8d Qh 4s 2c")),
+            (PlayerId(2), hand("As Kc 9d 9h 2d")),
+        ];
+
+        assert_eq!(showdown_winners(&hands), [PlayerId(0), PlayerId(2)]);
+        assert_eq!(showdown_winners(&hands[1..2]), [PlayerId(1)]);
+        assert_eq!(showdown_winners(&hands[..2]), [PlayerId(0)]);
+        assert_eq!(showdown_winners(&[]), []);
+    }
+
+    #[test]
+    fn board_chop() {
+        use crate::card::parse_cards;
+        use crate::hand::form_best_hand;
+
+        // No hole card can improve on the board's straight.
+        let board = parse_cards("Th Jd Qc Ks Ah").unwrap();
+
+        let hands: Vec<(PlayerId, Hand)> = ["2c 7d", "Ts Jc", "9h 9s", "Ad Kh"].iter()
+            .enumerate()
+            .map(|(i, hole)| {
+                let hole = parse_cards(hole).unwrap();
+                (PlayerId(i as u32), form_best_hand(&board, &hole).unwrap())
+            })
+            .collect();
+
+        assert!(hands.iter().all(|(_, hand)| *hand == hands[0].1));
+        assert_eq!(showdown_winners(&hands), [PlayerId(0), PlayerId(1), PlayerId(2), PlayerId(3)]);
+    }
+
+    #[test]
+    fn pots_track_players_by_id() {
+        let mut players = [
+            Player::new(PlayerId(0), "a", 100),
+            Player::new(PlayerId(1), "b", 30),
+            Player::new(PlayerId(2), "c", 100)
+        ];
+
+        let mut round = Round::new(Deck::generate());
+
+        // The pot refers to players by id, so both can change side by side.
+        for player in players.iter_mut() {
+            round.main_pot.size += player.bet(50).unwrap();
+            round.main_pot.eligible.push(player.id);
+        }
+
+        players[2].fold();
+        round.main_pot.eligible.retain(|&id| id != players[2].id);
+
+        assert_eq!(round.main_pot, Pot { size: 130, eligible: vec![PlayerId(0), PlayerId(1)] });
+        assert_eq!(players[1].status, PlayerStatus::AllIn);
+        assert_eq!(players[0].money, 50);
+    }
+
+    #[test]
+    fn pot_distribution() {
+        let a = Player::new(PlayerId(0), "a", 0);
+        let b = Player::new(PlayerId(1), "b", 0);
+        let c = Player::new(PlayerId(2), "c", 0);
+
+        let pot = Pot { size: 101, eligible: vec![a.id, b.id, c.id] };
+
+        let total = |awards: &[(PlayerId, u32)]| awards.iter().map(|&(_, n)| n).sum::<u32>();
+
+        let awards = distribute(&pot, &[&b]);
+        assert_eq!(awards, vec![(b.id, 101)]);
+        assert_eq!(total(&awards), pot.size);
+
+        let awards = distribute(&pot, &[&c, &a]);
+        assert_eq!(awards, vec![(c.id, 51), (a.id, 50)]);
+        assert_eq!(total(&awards), pot.size);
+
+        let awards = distribute(&pot, &[&a, &b, &c]);
+        assert_eq!(awards, vec![(a.id, 34), (b.id, 34), (c.id, 33)]);
+        assert_eq!(total(&awards), pot.size);
+
+        let pot = Pot { size: 99, eligible: vec![a.id, b.id, c.id] };
+
+        let awards = distribute(&pot, &[&a, &b, &c]);
+        assert_eq!(awards, vec![(a.id, 33), (b.id, 33), (c.id, 33)]);
+        assert_eq!(total(&awards), pot.size);
+    }
+
+    #[test]
+    fn betting_chips() {
+        let mut player = Player::new(PlayerId(0), "a", 100);
+
+        assert_eq!(player.bet(30), Ok(30));
+        assert_eq!(player.money, 70);
+        assert_eq!(player.status, PlayerStatus::Active);
+
+        // Over-betting commits the remaining stack.
+        assert_eq!(player.bet(500), Ok(70));
+        assert_eq!(player.status, PlayerStatus::AllIn);
+        assert!(player.is_busted());
+
+        assert_eq!(player.bet(10), Err(BetError::NotActive(PlayerStatus::AllIn)));
+    }
+
+    #[test]
+    fn stacks_in_big_blinds() {
+        let player = Player::new(PlayerId(0), "a", 3000);
+
+        assert_eq!(player.stack_in_bb(150), 20.0);
+        assert_eq!(stack_in_bb(250, 100), 2.5);
+        assert_eq!(stack_in_bb(0, 100), 0.0);
+        assert_eq!(player.stack_in_bb(0), f64::INFINITY);
+    }
+
+    #[test]
+    fn effective_stacks() {
+        let short = Player::new(PlayerId(0), "a", 400);
+        let deep = Player::new(PlayerId(1), "b", 2500);
+        let deeper = Player::new(PlayerId(2), "c", 1800);
+
+        assert_eq!(effective_stack(&short, &deep), 400);
+        assert_eq!(effective_stack(&deep, &short), 400);
+        assert_eq!(effective_stack_multiway(&[&short, &deep, &deeper]), 1800);
+        assert_eq!(effective_stack_multiway(&[&deep]), 0);
+
+        let table = players(&[1000, 1000, 1000, 1000]);
+
+        assert_eq!(effective_stack(&table[0], &table[1]), 1000);
+        assert_eq!(effective_stack_multiway(&table.iter().collect::<Vec<_>>()), 1000);
+    }
+
+    #[test]
+    fn folding() {
+        let mut player = Player::new(PlayerId(0), "a", 100);
+
+        player.fold();
+
+        assert_eq!(player.status, PlayerStatus::Folded);
+        assert_eq!(player.bet(10), Err(BetError::NotActive(PlayerStatus::Folded)));
+        assert_eq!(player.money, 100);
+    }
+
+    #[test]
+    fn blind_levels() {
+        let schedule = BlindSchedule::new(
+            vec![(10, 20, 0), (20, 40, 5), (50, 100, 10)],
+            std::time::Duration::from_secs(15 * 60)
+        );
+
+        let mut tournament = Tournament::new(Vec::new(), schedule);
+
+        assert_eq!(tournament.blinds, (10, 20));
+        assert_eq!(tournament.current_ante(), 0);
+
+        tournament.advance_level();
+        assert_eq!(tournament.blinds, (20, 40));
+        assert_eq!(tournament.current_ante(), 5);
+
+        for _ in 0..5 {
+            tournament.advance_level();
+        }
+
+        assert_eq!(tournament.level, 2);
+        assert_eq!(tournament.blinds, (50, 100));
+        assert_eq!(tournament.current_ante(), 10);
+    }
+
+    fn tournament_with_stacks(stacks: &[u32]) -> Tournament {
+        let players = stacks.iter()
+            .enumerate()
+            .map(|(i, &money)| Player::new(PlayerId(i as u32), &format!("p{}", i), money))
+            .collect();
+
+        let schedule = BlindSchedule::new(vec![(10, 20, 0)], std::time::Duration::from_secs(600));
+
+        Tournament::new(players, schedule)
+    }
+
+    #[test]
+    fn ids_survive_elimination() {
+        let mut tournament = tournament_with_stacks(&[100, 0, 100, 100]);
+
+        tournament.start_round();
+
+        assert!(tournament.player(PlayerId(1)).is_none());
+        assert_eq!(tournament.player(PlayerId(2)).unwrap().name, "p2");
+        assert_eq!(tournament.players[1].id, PlayerId(2));
+
+        // Moving players to other seats does not change who they are.
+        tournament.players.swap(0, 2);
+
+        assert_eq!(tournament.player(PlayerId(0)).unwrap().name, "p0");
+        assert_eq!(tournament.player(PlayerId(3)).unwrap().name, "p3");
+        assert_eq!(tournament.players[0].id, PlayerId(3));
+    }
+
+    #[test]
+    fn snapshots_survive_restoring() {
+        let mut tournament = tournament_with_stacks(&[100, 250, 0]);
+
+        tournament.start_round();
+        tournament.players[0].fold();
+        tournament.deck = Deck::shuffled_from_seed(3);
+        tournament.deck.draw_n(9).unwrap();
+
+        let snapshot = tournament.snapshot();
+        let restored = Tournament::restore(snapshot.clone());
+
+        assert_eq!(restored.snapshot(), snapshot);
+        assert_eq!(snapshot.players.len(), 2);
+        assert_eq!(snapshot.players[0].status, PlayerStatus::Folded);
+        assert_eq!(restored.dealer, tournament.dealer);
+        assert_eq!(restored.blinds, (10, 20));
+        assert_eq!(restored.deck.cards(), tournament.deck.cards());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn snapshot_json_round_trip() {
+        let mut tournament = tournament_with_stacks(&[100, 250, 80]);
+
+        tournament.advance_level();
+        tournament.deck = Deck::shuffled_from_seed(5);
+
+        let json = serde_json::to_string(&tournament.snapshot()).unwrap();
+        let snapshot: GameSnapshot = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(snapshot, tournament.snapshot());
+        assert_eq!(Tournament::restore(snapshot).snapshot(), tournament.snapshot());
+    }
+
+    #[test]
+    fn returning_player_posts_dead_blind() {
+        let mut tournament = tournament_with_stacks(&[1000, 1000, 1000, 1000]);
+
+        assert!(!tournament.return_player(PlayerId(0)));
+
+        tournament.miss_blinds(PlayerId(0));
+        tournament.miss_blinds(PlayerId(0));
+
+        assert!(tournament.return_player(PlayerId(0)));
+        assert!(!tournament.return_player(PlayerId(0)));
+
+        // The button moves to seat 1, so the returning player in seat 0 is
+        // first to act.
+        let start = tournament.start_round().unwrap();
+
+        assert_eq!(start.blinds, (2, 3));
+        assert_eq!(start.postings, [BlindPosting { player: PlayerId(0), dead: 10, live: 20 }]);
+
+        let dealer = tournament.dealer as usize;
+
+        let seated = (0..4)
+            .map(|i| &tournament.players[(dealer + i) % 4])
+            .map(|player| Player::new(player.id, &player.name, player.money))
+            .collect();
+
+        let mut round = Round::with_players(Deck::shuffled_from_seed(80), seated, (10, 20));
+        round.postings = start.postings;
+
+        let mut first = None;
+
+        let result = round.play(|state| {
+            first.get_or_insert(state.betting.seats()[3]);
+
+            if state.betting.current_bet() > 0 { Action::Call } else { Action::Check }
+        });
+
+        // Only the live blind counts towards the bet, which is already matched.
+        let returning = first.unwrap();
+
+        assert_eq!((returning.bet, returning.ante, returning.stack), (20, 10, 970));
+        assert_eq!(round.contributions[&PlayerId(0)], 30);
+        assert_eq!(result.winnings.iter().map(|&(_, won)| won).sum::<u32>(), 90);
+        assert_eq!(round.players.iter().map(|player| player.money).sum::<u32>(), 4000);
+
+        // A player returning into the big blind owes nothing more.
+        tournament.miss_blinds(PlayerId(0));
+        tournament.return_player(PlayerId(0));
+
+        let start = tournament.start_round().unwrap();
+
+        assert_eq!(start.blinds, (3, 0));
+        assert_eq!(start.postings, []);
+    }
+
+    #[test]
+    fn seeded_tournaments_deal_alike() {
+        let names: Vec<String> = ["a", "b", "c"].iter().map(|name| name.to_string()).collect();
+
+        let deal = |seed| {
+            let mut tournament = Tournament::new_seeded(names.clone(), 1500, (10, 20), seed);
+            let mut holes = Vec::new();
+
+            for _ in 0..3 {
+                tournament.start_round();
+                tournament.shuffle_deck();
+
+                for player in &tournament.players {
+                    holes.push((player.id, tournament.deck.draw_n(2).unwrap()));
+                }
+            }
+
+            holes
+        };
+
+        assert_eq!(deal(11), deal(11));
+        assert_ne!(deal(11), deal(12));
+
+        let tournament = Tournament::new_seeded(names.clone(), 1500, (10, 20), 11);
+
+        assert_eq!(tournament.players[2].name, "c");
+        assert_eq!(tournament.players[2].money, 1500);
+        assert_eq!(tournament.blinds, (10, 20));
+        assert_eq!(tournament.deck.remaining(), 52);
+
+        // A restored tournament goes on shuffling like the original.
+        let mut original = Tournament::new_seeded(names, 1500, (10, 20), 11);
+
+        original.shuffle_deck();
+
+        let mut restored = Tournament::restore(original.snapshot());
+
+        original.shuffle_deck();
+        restored.shuffle_deck();
+
+        assert_eq!(restored.deck.cards(), original.deck.cards());
+        assert_eq!(restored.snapshot(), original.snapshot());
+    }
+
+    #[test]
+    fn scoreboard_lists_busted_players_last() {
+        let mut tournament = tournament_with_stacks(&[600, 1500, 900]);
+
+        assert_eq!(tournament.scoreboard(), "p1 1500, p2 900, p0 600");
+
+        tournament.players[1].money = 0;
+        tournament.players[2].money = 2400;
+
+        assert_eq!(tournament.scoreboard(), "p2 2400, p0 600, p1 OUT");
+    }
+
+    #[test]
+    fn button_moves_past_busted_dealer() {
+        let mut tournament = tournament_with_stacks(&[100, 100, 100, 100]);
+
+        assert_eq!(tournament.start_round().unwrap().blinds, (2, 3));
+        assert_eq!(tournament.dealer, 1);
+
+        // The player on the button busts, as does the next one along.
+        tournament.players[1].money = 0;
+        tournament.players[2].money = 0;
+
+        assert_eq!(tournament.start_round().unwrap().blinds, (1, 0));
+        assert_eq!(tournament.players.len(), 2);
+        assert_eq!(tournament.players[tournament.dealer as usize].id, PlayerId(3));
+    }
+
+    #[test]
+    fn heads_up_blinds() {
+        let mut tournament = tournament_with_stacks(&[100, 100, 0]);
+
+        // The dealer posts the small blind.
+        assert_eq!(tournament.start_round().unwrap().blinds, (1, 0));
+        assert_eq!(tournament.start_round().unwrap().blinds, (0, 1));
+
+        tournament.players[1].money = 0;
+
+        assert_eq!(tournament.start_round(), None);
+    }
+}
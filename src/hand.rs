@@ -0,0 +1,1465 @@
+use itertools::Itertools;
+
+use crate::card::{parse_cards, Card, CardRank, CardSuit, ParseCardError};
+use crate::deck::{random_cards, Deck};
+use crate::eval::{evaluate_7, HandRank};
+
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum HandCategory {
+    HighCard,
+    Pair,
+    TwoPair,
+    ThreeOfAKind,
+    Straight,
+    Flush,
+    FullHouse,
+    FourOfAKind,
+    StraightFlush,
+    RoyalFlush
+}
+
+impl std::fmt::Display for HandCategory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use HandCategory::*;
+
+        match self {
+            HighCard      => "High Card",
+            Pair          => "Pair",
+            TwoPair       => "Two Pair",
+            ThreeOfAKind  => "Three of a Kind",
+            Straight      => "Straight",
+            Flush         => "Flush",
+            FullHouse     => "Full House",
+            FourOfAKind   => "Four of a Kind",
+            StraightFlush => "Straight Flush",
+            RoyalFlush    => "Royal Flush"
+        }.fmt(f)
+    }
+}
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Ruleset {
+    #[default]
+    Standard,
+    // Six-plus Hold'em: Twos through Fives are removed from the deck, a flush
+    // beats a full house and A-6-7-8-9 is the lowest straight.
+    ShortDeck
+}
+
+impl Ruleset {
+    // The strength of 'category' relative to the other categories.
+    fn category_strength(&self, category: HandCategory) -> u8 {
+        use HandCategory::*;
+
+        match (self, category) {
+            (Ruleset::ShortDeck, Flush)     => FullHouse as u8,
+            (Ruleset::ShortDeck, FullHouse) => Flush as u8,
+            _                               => category as u8
+        }
+    }
+
+    // The cards below the ace in the ace-low straight, highest first.
+    fn wheel(&self) -> [CardRank; 4] {
+        use CardRank::*;
+
+        match self {
+            Ruleset::Standard  => [Five, Four, Three, Two],
+            Ruleset::ShortDeck => [Nine, Eight, Seven, Six]
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum Outcome {
+    Win,
+    Loss,
+    Tie
+}
+
+// What separated the winning hand from the losing one.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum Decider {
+    // The hands are of different categories; holds the winning category.
+    Category(HandCategory),
+    // The cards at the given significance position differ in rank and are
+    // part of what makes up the category, e.g. the second pair of two pair.
+    Card(usize),
+    // As above, but the cards are kickers.
+    Kicker(usize)
+}
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct Showdown {
+    pub outcome: Outcome,
+    // Nothing if the hands tie.
+    pub decider: Option<Decider>
+}
+
+impl HandCategory {
+    fn max_by_strength(self, other: HandCategory, ruleset: Ruleset) -> HandCategory {
+        if ruleset.category_strength(self) >= ruleset.category_strength(other) {
+            self
+        }
+        else {
+            other
+        }
+    }
+
+    // How many of the five significance-ordered cards make up the category;
+    // the rest are kickers.
+    fn defining_cards(&self) -> usize {
+        use HandCategory::*;
+
+        match self {
+            HighCard     => 1,
+            Pair         => 2,
+            TwoPair      => 4,
+            ThreeOfAKind => 3,
+            FourOfAKind  => 4,
+            _            => 5
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Hand {
+    category: HandCategory,
+    ruleset: Ruleset,
+    // The order of 'cards' is significant in comparing the ranks of two hands.
+    // The card(s) that define the hand category come first and in descending
+    // rank order. If there are one or more kickers, they follow the
+    // aforementioned cards in descending rank order.
+    // The sorting method ranks ace lowest if doing so results
+    // in a stronger hand (e.g. a "five-high straight" over an "ace high").
+    cards: [Card; 5]
+}
+
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum HandError {
+    // The same card appears more than once.
+    Duplicate(Card),
+    // A hand has exactly five cards, not the given number.
+    WrongCount(usize),
+    Parse(ParseCardError)
+}
+
+impl std::fmt::Display for HandError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HandError::Duplicate(card) => write!(f, "card {} appears more than once", card),
+            HandError::WrongCount(n) => write!(f, "a hand has five cards, not {}", n),
+            HandError::Parse(error) => error.fmt(f)
+        }
+    }
+}
+
+impl std::error::Error for HandError {}
+
+impl Hand {
+    // The cards must be distinct, which is not checked. Use 'try_new' for
+    // cards that come from an untrusted source.
+    pub fn new(cards: [Card; 5]) -> Hand {
+        Self::new_with_ruleset(cards, Ruleset::Standard)
+    }
+
+    pub fn new_with_ruleset(mut cards: [Card; 5], ruleset: Ruleset) -> Hand {
+        Hand {
+            category: Self::sort_and_categorize(&mut cards, ruleset),
+            ruleset,
+            cards
+        }
+    }
+
+    pub fn try_new(cards: [Card; 5]) -> Result<Hand, HandError> {
+        for (i, card) in cards.iter().enumerate() {
+            if cards[i + 1..].contains(card) {
+                return Err(HandError::Duplicate(*card));
+            }
+        }
+
+        Ok(Self::new(cards))
+    }
+
+    pub fn category(&self) -> HandCategory {
+        self.category
+    }
+
+    // The cards in significance order.
+    pub fn cards(&self) -> &[Card; 5] {
+        &self.cards
+    }
+
+    // Iterates over the cards in significance order.
+    pub fn iter(&self) -> std::slice::Iter<'_, Card> {
+        self.cards.iter()
+    }
+
+    // The most significant card, e.g. one of the trips of a full house.
+    pub fn high_card(&self) -> Card {
+        self.cards[0]
+    }
+
+    // Compares the hands and explains what decided the outcome.
+    pub fn compare(&self, other: &Hand) -> Showdown {
+        let outcome = match self.cmp(other) {
+            std::cmp::Ordering::Greater => Outcome::Win,
+            std::cmp::Ordering::Less    => Outcome::Loss,
+            std::cmp::Ordering::Equal   => Outcome::Tie
+        };
+
+        let decider = if outcome == Outcome::Tie {
+            None
+        }
+        else if self.category != other.category {
+            Some(Decider::Category(self.category.max_by_strength(other.category, self.ruleset)))
+        }
+        else {
+            let position = (0..5)
+                .find(|&i| self.cards[i].rank != other.cards[i].rank)
+                .unwrap();
+
+            if position < self.category.defining_cards() {
+                Some(Decider::Card(position))
+            }
+            else {
+                Some(Decider::Kicker(position))
+            }
+        };
+
+        Showdown { outcome, decider }
+    }
+
+    // Whether this hand is strictly better than the best hand made of 'cards',
+    // which must be five to seven cards; any other number never loses.
+    pub fn beats(&self, cards: &[Card]) -> bool {
+        match form_best_hand_stud(cards) {
+            Some(other) => *self > other,
+            None        => false
+        }
+    }
+
+    // A number that orders hands exactly like comparing them does: the
+    // category's strength sits in the high bits, followed by the ranks of the
+    // five significance-ordered cards at four bits each.
+    pub fn strength(&self) -> u32 {
+        self.cards.iter()
+            .fold(self.ruleset.category_strength(self.category) as u32, |strength, card| {
+                strength << 4 | card.rank as u32
+            })
+    }
+
+    // The fraction of all distinct five-card hands dealt from this hand's
+    // deck (36 cards for Short Deck) that this hand beats.
+    pub fn percentile(&self) -> f64 {
+        let table = strength_table(self.ruleset);
+        let strength = self.strength();
+
+        let i = table.strengths.partition_point(|&(s, _)| s < strength);
+        let beaten = table.strengths.get(i).map_or(table.total, |&(_, below)| below);
+
+        beaten as f64 / table.total as f64
+    }
+
+    // The ranks of the cards in significance order. Suits never matter when
+    // comparing two hands.
+    fn ranks(&self) -> [CardRank; 5] {
+        self.cards.map(|card| card.rank)
+    }
+
+    // Human-readable name of the hand, e.g. "Full House, Kings over Threes".
+    pub fn describe(&self) -> String {
+        use HandCategory::*;
+
+        // Thanks to the significance order, the defining ranks are found at
+        // fixed positions for each category.
+        let first = self.cards[0].rank;
+
+        match self.category {
+            HighCard      => format!("{}-high", first.name()),
+            Pair          => format!("Pair of {}", first.plural_name()),
+            TwoPair       => format!("Two Pair, {} and {}",
+                                     first.plural_name(),
+                                     self.cards[2].rank.plural_name()),
+            ThreeOfAKind  => format!("Three of a Kind, {}", first.plural_name()),
+            Straight      => format!("{}-high Straight", first.name()),
+            Flush         => format!("Flush, {} high", first.name()),
+            FullHouse     => format!("Full House, {} over {}",
+                                     first.plural_name(),
+                                     self.cards[3].rank.plural_name()),
+            FourOfAKind   => format!("Four of a Kind, {}", first.plural_name()),
+            StraightFlush => format!("{}-high Straight Flush", first.name()),
+            RoyalFlush    => "Royal Flush".to_string()
+        }
+    }
+
+    fn sort_and_categorize(cards: &mut [Card; 5], ruleset: Ruleset) -> HandCategory {
+        cards.sort();
+        cards.reverse();
+
+        // All cards have same suit.
+        let is_flush = cards.iter()
+            .all(|card| card.suit == cards[0].suit);
+
+        let is_straight = match straight_high_card(&cards.map(|card| card.rank), ruleset) {
+            Some(high) => {
+                // Ace-low straight, e.g. five-high. The ace goes to the bottom.
+                if high != cards[0].rank {
+                    cards.rotate_left(1);
+                }
+
+                true
+            }
+            None => false
+        };
+
+        if is_flush && is_straight {
+            // Only the ace-high straight flush is royal. A wheel has had its
+            // ace moved to the bottom by now.
+            if cards[0].rank == CardRank::Ace {
+                HandCategory::RoyalFlush
+            }
+            else {
+                HandCategory::StraightFlush
+            }
+        }
+        else if is_flush {
+            HandCategory::Flush
+        }
+        else if is_straight {
+            HandCategory::Straight
+        }
+        else {
+            let mut t: Vec<Vec<Card>> = Vec::with_capacity(5);
+
+            // Group cards by rank.
+            for (_, group) in &(*cards).into_iter().group_by(|card| card.rank) {
+                t.push(group.collect());
+            }
+
+            // Sort by group length and then card rank.
+            t.sort_by(|a, b| match b.len().cmp(&a.len()) {
+                std::cmp::Ordering::Equal => b[0].rank.cmp(&a[0].rank),
+                o => o
+            });
+
+            // Copy the order.
+            for (i, &card) in t.iter().flatten().enumerate() {
+                cards[i] = card;
+            }
+
+            match t[0].len() {
+                4 => HandCategory::FourOfAKind,
+
+                3 => match t[1].len() {
+                    2 => HandCategory::FullHouse,
+                    _ => HandCategory::ThreeOfAKind
+                },
+
+                2 => match t[1].len() {
+                    2 => HandCategory::TwoPair,
+                    _ => HandCategory::Pair
+                },
+
+                _ => HandCategory::HighCard
+            }
+        }
+    }
+}
+
+// Hands compare by category strength under their ruleset and then by the
+// ranks of the significance-ordered cards.
+impl Ord for Hand {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.ruleset.category_strength(self.category)
+            .cmp(&other.ruleset.category_strength(other.category))
+            .then_with(|| self.ranks().cmp(&other.ranks()))
+    }
+}
+
+impl PartialOrd for Hand {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Hand {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == std::cmp::Ordering::Equal
+    }
+}
+
+impl Eq for Hand {}
+
+// The distinct strengths of all five-card hands under a ruleset, sorted, each
+// with the number of hands that are weaker.
+struct StrengthTable {
+    strengths: Vec<(u32, u64)>,
+    total: u64
+}
+
+impl StrengthTable {
+    fn build(ruleset: Ruleset) -> StrengthTable {
+        let mut deck = Deck::empty();
+
+        match ruleset {
+            Ruleset::Standard  => deck.reset(),
+            Ruleset::ShortDeck => deck.reset_short()
+        }
+
+        let cards = deck.draw_n(deck.remaining()).unwrap();
+
+        let all: Vec<u32> = cards.into_iter()
+            .combinations(5)
+            .map(|cards| Hand::new_with_ruleset(cards.try_into().unwrap(), ruleset).strength())
+            .sorted_unstable()
+            .collect();
+
+        let mut strengths = Vec::new();
+        let mut below = 0;
+
+        for (count, strength) in all.iter().dedup_with_count() {
+            strengths.push((*strength, below));
+            below += count as u64;
+        }
+
+        StrengthTable { strengths, total: below }
+    }
+}
+
+// Built on first use, which takes a moment.
+fn strength_table(ruleset: Ruleset) -> &'static StrengthTable {
+    use std::sync::OnceLock;
+
+    static STANDARD: OnceLock<StrengthTable> = OnceLock::new();
+    static SHORT_DECK: OnceLock<StrengthTable> = OnceLock::new();
+
+    match ruleset {
+        Ruleset::Standard  => STANDARD.get_or_init(|| StrengthTable::build(ruleset)),
+        Ruleset::ShortDeck => SHORT_DECK.get_or_init(|| StrengthTable::build(ruleset))
+    }
+}
+
+impl From<&Hand> for HandRank {
+    fn from(hand: &Hand) -> HandRank {
+        HandRank::new(hand.category, hand.cards.map(|card| card.rank as u8))
+    }
+}
+
+// Parses five distinct card codes, e.g. "Ah Kh Qh Jh Th".
+impl std::str::FromStr for Hand {
+    type Err = HandError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_cards(s).map_err(HandError::Parse)?.try_into()
+    }
+}
+
+impl TryFrom<&[Card]> for Hand {
+    type Error = HandError;
+
+    fn try_from(cards: &[Card]) -> Result<Self, Self::Error> {
+        let cards: [Card; 5] = cards.try_into()
+            .map_err(|_| HandError::WrongCount(cards.len()))?;
+
+        Hand::try_new(cards)
+    }
+}
+
+impl TryFrom<Vec<Card>> for Hand {
+    type Error = HandError;
+
+    fn try_from(cards: Vec<Card>) -> Result<Self, Self::Error> {
+        Hand::try_from(cards.as_slice())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Hand {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let mut hand = serializer.serialize_struct("Hand", 3)?;
+        hand.serialize_field("category", &self.category)?;
+        hand.serialize_field("ruleset", &self.ruleset)?;
+        hand.serialize_field("cards", &self.cards)?;
+        hand.end()
+    }
+}
+
+// The category and card order are recomputed rather than trusted.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Hand {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(serde::Deserialize)]
+        struct Fields {
+            #[serde(default)]
+            ruleset: Ruleset,
+            cards: [Card; 5]
+        }
+
+        let fields = Fields::deserialize(deserializer)?;
+
+        Hand::try_new(fields.cards)
+            .map(|_| Hand::new_with_ruleset(fields.cards, fields.ruleset))
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+// Prints the category followed by the cards in significance order.
+impl std::fmt::Display for Hand {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:", self.category)?;
+
+        for card in &self.cards {
+            write!(f, " {} {}", card.suit, card.rank)?;
+        }
+
+        Ok(())
+    }
+}
+
+// Like 'detect_straight', with the wheel of the given ruleset.
+fn straight_high_card(ranks: &[CardRank; 5], ruleset: Ruleset) -> Option<CardRank> {
+    let mut ranks = *ranks;
+    ranks.sort();
+    ranks.reverse();
+
+    let consecutive = |ranks: &[CardRank]| {
+        ranks.windows(2).all(|pair| pair[0].value() == pair[1].value() + 1)
+    };
+
+    if consecutive(&ranks) {
+        Some(ranks[0])
+    }
+    else if ranks[0] == CardRank::Ace && ranks[1..] == ruleset.wheel() {
+        Some(ranks[1])
+    }
+    else {
+        None
+    }
+}
+
+// The high card of the straight formed by 'ranks', in any order, if they form
+// one. The ace-low straight, or wheel, is five-high.
+pub fn detect_straight(ranks: &[CardRank; 5]) -> Option<CardRank> {
+    straight_high_card(ranks, Ruleset::Standard)
+}
+
+// The best Texas Hold'em hand. 'hole' holds up to two cards, any number of
+// which may be used, including none ("playing the board"), so every
+// five-card combination is considered. There is no hand with more than two
+// hole cards or fewer than five cards in total. Seven cards take the fast
+// path of 'evaluate_7'; otherwise 'num_hand_combinations' hands are compared.
+pub fn form_best_hand(community: &[Card], hole: &[Card]) -> Option<Hand>
+{
+    if hole.len() > 2 || community.len() + hole.len() < 5 {
+        return None;
+    }
+
+    let cards: Vec<Card> = community.iter().chain(hole).copied().collect();
+
+    best_hand_of(&cards)
+}
+
+// The number of five-card hands that can be made of 'total_cards' cards, and
+// so how many hands finding the best one compares.
+pub fn num_hand_combinations(total_cards: usize) -> usize {
+    if total_cards < 5 {
+        return 0;
+    }
+
+    (0..5).fold(1, |n, i| n * (total_cards - i) / (i + 1))
+}
+
+// The best five-card hand that can be made of any of 'cards', typically five
+// to seven of them. There is none if fewer than five cards are given.
+pub fn best_hand_of(cards: &[Card]) -> Option<Hand>
+{
+    if let Ok(seven) = cards.try_into() {
+        return Some(best_hand_of_7(seven));
+    }
+
+    cards.iter()
+        .copied()
+        .combinations(5)
+        .map(|cards| Hand::new(cards.try_into().unwrap()))
+        .max()
+}
+
+// The best hand when the cards of 'cards' that are among 'wilds', e.g. all
+// deuces in "deuces wild", may stand for any card not otherwise in the hand.
+// Every substitution is tried, so keep the number of wild cards small.
+pub fn best_hand_with_wild(cards: &[Card], wilds: &[Card]) -> Option<Hand>
+{
+    let (wild, natural): (Vec<Card>, Vec<Card>) = cards.iter()
+        .partition(|card| wilds.contains(card));
+
+    if wild.is_empty() {
+        return best_hand_of(&natural);
+    }
+
+    let mut deck = Deck::without(&natural);
+    let substitutes = deck.draw_n(deck.remaining()).unwrap();
+
+    substitutes.into_iter()
+        .combinations(wild.len())
+        .filter_map(|substitutes| best_hand_of(&[&natural[..], &substitutes[..]].concat()))
+        .max()
+}
+
+// The best hand of seven cards, built from the ranks 'evaluate_7' picks
+// rather than by comparing all 21 hands. Where several cards share a rank,
+// the one chosen may differ in suit from the one a full scan picks, but the
+// hands compare equal.
+fn best_hand_of_7(cards: &[Card; 7]) -> Hand {
+    use HandCategory::*;
+
+    let rank = evaluate_7(cards);
+
+    let flush_suit = match rank.category() {
+        Flush | StraightFlush | RoyalFlush => CardSuit::all()
+            .into_iter()
+            .find(|&suit| cards.iter().filter(|card| card.suit == suit).count() >= 5),
+        _ => None
+    };
+
+    let mut unused = cards.to_vec();
+
+    let five = rank.ranks().map(|rank| {
+        let i = unused.iter()
+            .position(|card| card.rank as u8 == rank && flush_suit.is_none_or(|suit| card.suit == suit))
+            .unwrap();
+
+        unused.swap_remove(i)
+    });
+
+    Hand::new(five)
+}
+
+// In Seven Card Stud there is no board; the best five of a player's own five
+// to seven cards make the hand.
+pub fn form_best_hand_stud(cards: &[Card]) -> Option<Hand>
+{
+    if !(5..=7).contains(&cards.len()) {
+        return None;
+    }
+
+    best_hand_of(cards)
+}
+
+// The best hand any two cards not on 'board' could make with it, whoever may
+// actually hold them. The board must have three to five cards.
+pub fn nut_hand(board: &[Card]) -> Hand {
+    assert!((3..=5).contains(&board.len()), "a board has three to five cards");
+
+    Deck::without(board).cards()
+        .iter()
+        .tuple_combinations()
+        .map(|(&a, &b)| form_best_hand(board, &[a, b]).unwrap())
+        .max()
+        .unwrap()
+}
+
+// The number of distinct five-card hands of each category, indexed by
+// 'HandCategory as usize'. Every hand of a full deck is evaluated, which takes
+// a while.
+#[cfg(feature = "exhaustive")]
+pub fn category_counts() -> [u64; 10] {
+    let mut counts = [0; 10];
+
+    for cards in crate::deck::all_five_card_hands() {
+        counts[Hand::new(cards).category as usize] += 1;
+    }
+
+    counts
+}
+
+pub fn random_hand(rng: &mut impl rand::RngCore) -> Hand {
+    Hand::new(random_cards(5, rng).try_into().unwrap())
+}
+
+// In Omaha a hand is made of exactly two of the four hole cards and exactly
+// three of the five community cards.
+pub fn form_best_hand_omaha(community: &[Card], hole: &[Card]) -> Option<Hand>
+{
+    if community.len() != 5 || hole.len() != 4 {
+        return None;
+    }
+
+    hole.iter()
+        .copied()
+        .combinations(2)
+        .cartesian_product(community.iter().copied().combinations(3))
+        .map(|(h, c)| {
+            let cards: Vec<Card> = h.into_iter().chain(c).collect();
+            Hand::new(cards.try_into().unwrap())
+        })
+        .max()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::card::CardSuit;
+    use CardRank::*;
+    use CardSuit::*;
+    use HandCategory::*;
+
+    #[allow(non_snake_case)]
+    const fn H(rank: CardRank) -> Card {
+        Card { suit: Hearts, rank }
+    }
+
+    #[allow(non_snake_case)]
+    const fn C(rank: CardRank) -> Card {
+        Card { suit: Clubs, rank }
+    }
+
+    #[allow(non_snake_case)]
+    const fn S(rank: CardRank) -> Card {
+        Card { suit: Spades, rank }
+    }
+
+    #[allow(non_snake_case)]
+    const fn D(rank: CardRank) -> Card {
+        Card { suit: Diamonds, rank }
+    }
+
+    #[test]
+    fn hand_categorization() {
+
+        let hand = Hand::new([
+            H(Jack),
+            H(Ten),
+            H(Ace),
+            H(King),
+            H(Queen),
+        ]);
+
+        assert_eq!(hand.category, RoyalFlush);
+
+        let hand = Hand::new([
+            H(Three),
+            H(Four),
+            H(Five),
+            H(Six),
+            H(Seven),
+        ]);
+
+        assert_eq!(hand.category, StraightFlush);
+
+        let hand = Hand::new([
+            H(Three),
+            H(Two),
+            H(Five),
+            H(Ace),
+            H(Seven),
+        ]);
+
+        assert_eq!(hand.category, Flush);
+
+        let hand = Hand::new([
+            D(Two),
+            H(Jack),
+            C(Two),
+            S(Two),
+            H(Two),
+        ]);
+
+        assert_eq!(hand.category, FourOfAKind);
+
+        // Five-high straight.
+        let hand = Hand::new([
+            H(Ace),
+            C(Four),
+            S(Five),
+            H(Three),
+            H(Two),
+        ]);
+
+        assert_eq!(hand.category, Straight);
+
+        // Ace-high straight.
+        let hand = Hand::new([
+            H(Jack),
+            H(Ten),
+            H(Ace),
+            S(Queen),
+            C(King),
+        ]);
+
+        assert_eq!(hand.category, Straight);
+
+        // Eight-high straight.
+        let hand = Hand::new([
+            H(Eight),
+            S(Six),
+            H(Five),
+            H(Four),
+            C(Seven),
+        ]);
+
+        assert_eq!(hand.category, Straight);
+
+        let hand = Hand::new([
+            H(Three),
+            D(Four),
+            S(Seven),
+            C(Seven),
+            H(Seven),
+        ]);
+
+        assert_eq!(hand.category, ThreeOfAKind);
+
+        let hand = Hand::new([
+            H(Four),
+            D(Four),
+            S(Seven),
+            C(Seven),
+            H(Seven),
+        ]);
+
+        assert_eq!(hand.category, FullHouse);
+
+        let hand = Hand::new([
+            H(Four),
+            D(Five),
+            S(Five),
+            C(Jack),
+            H(Jack),
+        ]);
+
+        assert_eq!(hand.category, TwoPair);
+
+        let hand = Hand::new([
+            H(Four),
+            D(Five),
+            S(Nine),
+            C(Jack),
+            H(Jack),
+        ]);
+
+        assert_eq!(hand.category, Pair);
+
+        let hand = Hand::new([
+            H(Four),
+            D(Five),
+            S(Nine),
+            C(Jack),
+            H(Two),
+        ]);
+
+        assert_eq!(hand.category, HighCard);
+    }
+
+    #[test]
+    fn hand_comparison() {
+        let king_high = Hand::new([
+            H(Four),
+            D(Five),
+            S(Three),
+            C(King),
+            H(Two),
+        ]);
+
+        let jack_high = Hand::new([
+            H(Four),
+            D(Five),
+            S(Nine),
+            C(Jack),
+            H(Two),
+        ]);
+
+        assert!(jack_high < king_high);
+    }
+
+    #[test]
+    fn beating_raw_cards() {
+        let kings = Hand::new([H(King), D(King), S(Nine), C(Five), H(Two)]);
+
+        assert!(kings.beats(&[H(Queen), D(Queen), S(Ace), C(Jack), H(Three)]));
+        assert!(!kings.beats(&[H(Ace), D(Ace), S(Four), C(Jack), H(Three), D(Eight), C(Six)]));
+
+        // Ties do not count as wins.
+        assert!(!kings.beats(&[S(King), C(King), D(Nine), H(Five), D(Two), C(Three)]));
+
+        assert!(!kings.beats(&[H(Three), D(Eight)]));
+    }
+
+    #[test]
+    fn two_pair_comparison() {
+        // Higher pair first, then the lower pair, then the kicker.
+        let kings_and_queens = Hand::new([H(Two), D(Queen), S(King), C(Queen), H(King)]);
+        let kings_and_jacks = Hand::new([S(Ace), D(Jack), D(King), C(Jack), C(King)]);
+        let queens_and_jacks = Hand::new([H(Ace), H(Jack), S(Queen), S(Jack), H(Queen)]);
+
+        assert!(kings_and_queens > kings_and_jacks);
+        assert!(kings_and_jacks > queens_and_jacks);
+
+        let ace_kicker = Hand::new([H(Ace), D(Nine), S(Nine), C(Four), H(Four)]);
+        let king_kicker = Hand::new([C(King), C(Nine), H(Nine), S(Four), D(Four)]);
+
+        assert!(ace_kicker > king_kicker);
+        assert_eq!(ace_kicker, Hand::new([S(Ace), H(Nine), D(Nine), S(Four), D(Four)]));
+
+        assert_eq!(kings_and_jacks.ranks(), [King, King, Jack, Jack, Ace]);
+    }
+
+    #[test]
+    fn flush_comparison() {
+        let nine = Hand::new([H(Nine), H(Ace), H(Jack), H(King), H(Queen)]);
+        let eight = Hand::new([S(Queen), S(Eight), S(King), S(Ace), S(Jack)]);
+
+        assert_eq!(nine.category, Flush);
+        assert_eq!(nine.ranks(), [Ace, King, Queen, Jack, Nine]);
+        assert_eq!(eight.ranks(), [Ace, King, Queen, Jack, Eight]);
+        assert!(nine > eight);
+
+        // Only the fifth card differs.
+        let three = Hand::new([D(Three), D(Nine), D(King), D(Six), D(Jack)]);
+        let deuce = Hand::new([C(Six), C(Two), C(Jack), C(Nine), C(King)]);
+
+        assert!(three > deuce);
+        assert_eq!(three.compare(&deuce).decider, Some(Decider::Card(4)));
+        assert_eq!(three, Hand::new([S(King), S(Jack), S(Nine), S(Six), S(Three)]));
+    }
+
+    #[test]
+    fn full_house_comparison() {
+        // The three of a kind decides before the pair.
+        let threes_over_aces = Hand::new([H(Three), D(Ace), S(Three), C(Ace), D(Three)]);
+        let twos_over_aces = Hand::new([H(Two), H(Ace), S(Two), S(Ace), D(Two)]);
+        let threes_over_kings = Hand::new([C(Three), D(King), S(Three), C(King), H(Three)]);
+
+        assert!(threes_over_aces > twos_over_aces);
+        assert!(threes_over_kings > twos_over_aces);
+        assert!(threes_over_aces > threes_over_kings);
+
+        assert_eq!(threes_over_aces.ranks(), [Three, Three, Three, Ace, Ace]);
+    }
+
+    #[test]
+    fn best_hand_playing_the_board() {
+        let community = [
+            S(Ten),
+            S(Jack),
+            S(Queen),
+            S(King),
+            S(Ace),
+        ];
+
+        let hole = [
+            H(Two),
+            D(Seven),
+        ];
+
+        let hand = form_best_hand(&community, &hole).unwrap();
+
+        assert_eq!(hand.category, RoyalFlush);
+
+        assert_eq!(form_best_hand(&community, &[]).unwrap().category, RoyalFlush);
+    }
+
+    #[test]
+    fn best_hand_input_validation() {
+        let community = [S(Ten), S(Jack), S(Queen)];
+
+        assert!(form_best_hand(&community, &[H(Two)]).is_none());
+        assert!(form_best_hand(&[], &[]).is_none());
+        assert!(form_best_hand(&community, &[H(Two), D(Seven), C(Ace)]).is_none());
+        assert_eq!(form_best_hand(&community, &[S(King), S(Ace)]).unwrap().category, RoyalFlush);
+    }
+
+    #[test]
+    fn hand_description() {
+        let describe = |cards| Hand::new(cards).describe();
+
+        assert_eq!(describe([H(Jack), H(Ten), H(Ace), H(King), H(Queen)]), "Royal Flush");
+        assert_eq!(describe([S(Three), S(Four), S(Five), S(Six), S(Seven)]), "Seven-high Straight Flush");
+        assert_eq!(describe([D(Two), H(Jack), C(Two), S(Two), H(Two)]), "Four of a Kind, Twos");
+        assert_eq!(describe([H(Three), D(King), S(King), C(Three), H(King)]), "Full House, Kings over Threes");
+        assert_eq!(describe([H(Three), H(Two), H(Queen), H(Nine), H(Seven)]), "Flush, Queen high");
+        assert_eq!(describe([H(Jack), H(Ten), H(Ace), S(Queen), C(King)]), "Ace-high Straight");
+        assert_eq!(describe([H(Ace), C(Four), S(Five), H(Three), H(Two)]), "Five-high Straight");
+        assert_eq!(describe([H(Six), D(Four), S(Seven), C(Six), H(Six)]), "Three of a Kind, Sixes");
+        assert_eq!(describe([H(Four), D(Five), S(Five), C(Jack), H(Jack)]), "Two Pair, Jacks and Fives");
+        assert_eq!(describe([H(Four), D(Seven), S(Nine), C(Seven), H(Jack)]), "Pair of Sevens");
+        assert_eq!(describe([H(Four), D(Five), S(King), C(Jack), H(Two)]), "King-high");
+    }
+
+    #[test]
+    fn hand_display() {
+        assert_eq!(RoyalFlush.to_string(), "Royal Flush");
+        assert_eq!(FullHouse.to_string(), "Full House");
+        assert_eq!(TwoPair.to_string(), "Two Pair");
+        assert_eq!(ThreeOfAKind.to_string(), "Three of a Kind");
+
+        let hand = Hand::new([H(Three), D(King), S(King), C(Three), H(King)]);
+
+        assert_eq!(hand.to_string(), "Full House: ♦ K ♥ K ♠ K ♣ 3 ♥ 3");
+
+        let hand = Hand::new([H(Ace), C(Four), S(Ten), H(Three), H(Four)]);
+
+        assert_eq!(hand.to_string(), "Pair: ♣ 4 ♥ 4 ♥ A ♠ 10 ♥ 3");
+    }
+
+    #[test]
+    fn omaha_uses_exactly_two_hole_cards() {
+        let community = [
+            H(Two),
+            C(Seven),
+            S(Nine),
+            D(Jack),
+            C(King),
+        ];
+
+        let hole = [
+            H(Ace),
+            H(King),
+            H(Queen),
+            H(Three),
+        ];
+
+        // Using any of the cards would allow the flush.
+        let any = best_hand_of(&[&community[..], &hole[..]].concat()).unwrap();
+        assert_eq!(any.category, Flush);
+
+        let omaha = form_best_hand_omaha(&community, &hole).unwrap();
+        assert_eq!(omaha.category, Pair);
+        assert_eq!(omaha.cards[0].rank, King);
+
+        assert!(form_best_hand_omaha(&community[..4], &hole).is_none());
+        assert!(form_best_hand_omaha(&community, &hole[..2]).is_none());
+    }
+
+    #[test]
+    fn short_deck_rankings() {
+        let flush = [H(Six), H(Eight), H(Ten), H(Queen), H(King)];
+        let full_house = [S(Nine), D(Nine), C(Nine), H(Seven), S(Seven)];
+
+        assert!(Hand::new(flush) < Hand::new(full_house));
+
+        assert!(
+            Hand::new_with_ruleset(flush, Ruleset::ShortDeck) >
+            Hand::new_with_ruleset(full_house, Ruleset::ShortDeck)
+        );
+
+        let wheel = [H(Ace), C(Six), S(Seven), D(Eight), H(Nine)];
+
+        assert_eq!(Hand::new(wheel).category, HighCard);
+
+        let hand = Hand::new_with_ruleset(wheel, Ruleset::ShortDeck);
+        assert_eq!(hand.category, Straight);
+        assert_eq!(hand.cards[0].rank, Nine);
+
+        let ten_high = Hand::new_with_ruleset(
+            [H(Ten), C(Six), S(Seven), D(Eight), H(Nine)],
+            Ruleset::ShortDeck
+        );
+
+        assert!(hand < ten_high);
+    }
+
+    #[test]
+    fn duplicate_cards_are_rejected() {
+        let hand = Hand::try_new([H(Ace), H(Ace), S(Ace), C(Ace), D(Ace)]);
+
+        assert_eq!(hand.err(), Some(HandError::Duplicate(H(Ace))));
+
+        let hand = Hand::try_new([H(Ace), S(Ace), C(Ace), D(Ace), H(Two)]).unwrap();
+
+        assert_eq!(hand.category, FourOfAKind);
+    }
+
+    #[test]
+    fn hand_from_slice() {
+        let cards = [H(Ace), S(Ace), C(Seven), D(Seven), H(Two), S(Two)];
+
+        assert_eq!(Hand::try_from(&cards[..4]).err(), Some(HandError::WrongCount(4)));
+        assert_eq!(Hand::try_from(&cards[..]).err(), Some(HandError::WrongCount(6)));
+        assert_eq!(Hand::try_from(&cards[..5]).unwrap().category, TwoPair);
+
+        assert_eq!(
+            Hand::try_from(vec![H(Ace), S(Ace), C(Seven), D(Seven), H(Ace)]).err(),
+            Some(HandError::Duplicate(H(Ace)))
+        );
+        assert_eq!(Hand::try_from(cards[1..].to_vec()).unwrap().category, TwoPair);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        let hand = Hand::new([H(Three), D(King), S(King), C(Three), H(King)]);
+
+        let json = serde_json::to_string(&hand).unwrap();
+
+        assert_eq!(
+            json,
+            r#"{"category":"FullHouse","ruleset":"Standard","cards":["Kd","Kh","Ks","3c","3h"]}"#
+        );
+
+        let restored: Hand = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(restored, hand);
+        assert_eq!(restored.category, FullHouse);
+        assert_eq!(restored.cards, hand.cards);
+
+        let duplicated = r#"{"cards":["Kd","Kd","Ks","3c","3h"]}"#;
+
+        assert!(serde_json::from_str::<Hand>(duplicated).is_err());
+    }
+
+    #[test]
+    fn hand_parsing() {
+        let hand: Hand = "Ah Kh Qh Jh Th".parse().unwrap();
+
+        assert_eq!(hand.category, RoyalFlush);
+
+        assert_eq!(
+            "Ah Kh Qx Jh Th".parse::<Hand>().err(),
+            Some(HandError::Parse(ParseCardError::BadToken {
+                index: 2,
+                error: Box::new(ParseCardError::BadSuit)
+            }))
+        );
+
+        assert_eq!("Ah Kh Qh Jh".parse::<Hand>().err(), Some(HandError::WrongCount(4)));
+        assert_eq!("Ah Kh Qh Jh Th 9h".parse::<Hand>().err(), Some(HandError::WrongCount(6)));
+        assert_eq!("Ah Kh Qh Jh Ah".parse::<Hand>().err(), Some(HandError::Duplicate(H(Ace))));
+    }
+
+    #[test]
+    fn random_hands_have_distinct_cards() {
+        use rand::{rngs::StdRng, SeedableRng};
+
+        let mut rng = StdRng::seed_from_u64(29);
+
+        for _ in 0..1000 {
+            let hand = random_hand(&mut rng);
+
+            assert!(Hand::try_new(hand.cards).is_ok());
+        }
+    }
+
+    #[test]
+    fn hand_accessors() {
+        let hand = Hand::new([H(Three), D(King), S(Three), C(Three), H(King)]);
+
+        assert_eq!(hand.category(), FullHouse);
+        assert_eq!(hand.high_card().rank, Three);
+
+        let cards = hand.cards();
+
+        assert!(cards[..3].iter().all(|card| card.rank == Three));
+        assert!(cards[3..].iter().all(|card| card.rank == King));
+    }
+
+    #[test]
+    fn showdown_explanation() {
+        let aces_king = Hand::new([H(Ace), D(Ace), S(King), C(Seven), H(Two)]);
+        let aces_queen = Hand::new([C(Ace), S(Ace), D(Queen), H(Seven), D(Two)]);
+
+        assert_eq!(aces_king.compare(&aces_queen), Showdown {
+            outcome: Outcome::Win,
+            decider: Some(Decider::Kicker(2))
+        });
+
+        assert_eq!(aces_queen.compare(&aces_king), Showdown {
+            outcome: Outcome::Loss,
+            decider: Some(Decider::Kicker(2))
+        });
+
+        let kings_up = Hand::new([H(King), D(King), S(Four), C(Four), H(Two)]);
+        let kings_fives = Hand::new([C(King), S(King), D(Five), H(Five), D(Two)]);
+
+        assert_eq!(kings_up.compare(&kings_fives).decider, Some(Decider::Card(2)));
+
+        let flush = Hand::new([H(Three), H(Two), H(Queen), H(Nine), H(Seven)]);
+
+        assert_eq!(aces_king.compare(&flush), Showdown {
+            outcome: Outcome::Loss,
+            decider: Some(Decider::Category(Flush))
+        });
+
+        // Identical ranks in different suits.
+        let same_ranks = Hand::new([S(Ace), C(Ace), D(King), H(Seven), S(Two)]);
+
+        assert_eq!(aces_king.compare(&same_ranks), Showdown {
+            outcome: Outcome::Tie,
+            decider: None
+        });
+    }
+
+    #[test]
+    fn stud_best_five_of_seven() {
+        let cards = [
+            C(Nine),
+            D(Nine),
+            C(Two),
+            C(Jack),
+            S(Four),
+            C(Six),
+            C(King),
+        ];
+
+        let hand = form_best_hand_stud(&cards).unwrap();
+
+        assert_eq!(hand.category(), Flush);
+        assert_eq!(hand.high_card().rank, King);
+        assert!(hand.cards().iter().all(|card| card.suit == Clubs));
+
+        assert_eq!(form_best_hand_stud(&cards[..5]).unwrap().category(), Pair);
+        assert!(form_best_hand_stud(&cards[..4]).is_none());
+    }
+
+    #[test]
+    fn strength_matches_ordering() {
+        use rand::{rngs::StdRng, SeedableRng};
+
+        let mut rng = StdRng::seed_from_u64(37);
+
+        for _ in 0..10_000 {
+            let a = random_hand(&mut rng);
+            let b = random_hand(&mut rng);
+
+            assert_eq!(a.strength().cmp(&b.strength()), a.cmp(&b));
+        }
+
+        let flush = [H(Six), H(Eight), H(Ten), H(Queen), H(King)];
+        let full_house = [S(Nine), D(Nine), C(Nine), H(Seven), S(Seven)];
+
+        assert!(
+            Hand::new_with_ruleset(flush, Ruleset::ShortDeck).strength() >
+            Hand::new_with_ruleset(full_house, Ruleset::ShortDeck).strength()
+        );
+    }
+
+    #[test]
+    fn hand_percentile() {
+        let royal = Hand::new([H(Jack), H(Ten), H(Ace), H(King), H(Queen)]);
+
+        assert!((royal.percentile() - 1.0).abs() < 1e-5);
+
+        // Beats nothing but loses to every other hand.
+        let worst = Hand::new([H(Seven), D(Five), S(Four), C(Three), H(Two)]);
+
+        assert_eq!(worst.percentile(), 0.0);
+
+        // Ties do not count as beaten: only the 1,302,540 high card hands
+        // rank below any pair.
+        let worst_pair = Hand::new([H(Two), D(Two), S(Five), C(Four), H(Three)]);
+
+        assert_eq!(worst_pair.percentile(), 1_302_540.0 / 2_598_960.0);
+    }
+
+    #[test]
+    fn straight_detection() {
+        assert_eq!(detect_straight(&[Ace, King, Queen, Jack, Ten]), Some(Ace));
+        assert_eq!(detect_straight(&[Two, Ace, Four, Five, Three]), Some(Five));
+        assert_eq!(detect_straight(&[Four, Five, Six, Seven, Eight]), Some(Eight));
+        assert_eq!(detect_straight(&[Ace, King, Queen, Jack, Nine]), None);
+        assert_eq!(detect_straight(&[King, Ace, Two, Three, Four]), None);
+        assert_eq!(detect_straight(&[Five, Five, Four, Three, Two]), None);
+
+        assert_eq!(straight_high_card(&[Ace, Six, Seven, Eight, Nine], Ruleset::ShortDeck), Some(Nine));
+        assert_eq!(detect_straight(&[Ace, Six, Seven, Eight, Nine]), None);
+
+        // The wheel's ace is moved to the bottom.
+        let wheel = Hand::new([H(Ace), S(Two), D(Three), C(Four), H(Five)]);
+
+        assert_eq!(wheel.cards()[0].rank, Five);
+        assert_eq!(wheel.cards()[4].rank, Ace);
+    }
+
+    #[test]
+    fn straight_flush_boundaries() {
+        let royal = Hand::new([S(Ace), S(King), S(Queen), S(Jack), S(Ten)]);
+        let king_high = Hand::new([S(King), S(Queen), S(Jack), S(Ten), S(Nine)]);
+        let wheel = Hand::new([S(Ace), S(Two), S(Three), S(Four), S(Five)]);
+
+        assert_eq!(royal.category, RoyalFlush);
+        assert_eq!(king_high.category, StraightFlush);
+        assert_eq!(wheel.category, StraightFlush);
+        assert_eq!(wheel.ranks(), [Five, Four, Three, Two, Ace]);
+
+        // High cards of one suit without a straight are only a flush.
+        assert_eq!(Hand::new([S(Ace), S(King), S(Queen), S(Jack), S(Nine)]).category, Flush);
+
+        assert!(royal > king_high);
+        assert!(king_high > wheel);
+    }
+
+    #[test]
+    fn nuts_on_the_board() {
+        // Four spades without a straight flush draw make the ace-high flush
+        // the nuts.
+        let board = [S(King), S(Nine), S(Four), S(Two), H(Seven)];
+        let nuts = nut_hand(&board);
+
+        assert_eq!(nuts.category, Flush);
+        assert_eq!(nuts.ranks(), [Ace, King, Queen, Nine, Four]);
+
+        // With connected spades the straight flush is possible instead.
+        let board = [S(Nine), S(Eight), S(Seven), S(Six), D(Two)];
+
+        assert_eq!(nut_hand(&board).category, StraightFlush);
+        assert_eq!(nut_hand(&board).ranks(), [Jack, Ten, Nine, Eight, Seven]);
+
+        // Quads are the best a dry paired flop can give.
+        assert_eq!(nut_hand(&[H(Jack), S(Jack), D(Three)]).category, FourOfAKind);
+    }
+
+    #[test]
+    fn hand_combination_counts() {
+        assert_eq!(num_hand_combinations(7), 21);
+        assert_eq!(num_hand_combinations(6), 6);
+        assert_eq!(num_hand_combinations(5), 1);
+        assert_eq!(num_hand_combinations(4), 0);
+        assert_eq!(num_hand_combinations(52), 2_598_960);
+    }
+
+    #[test]
+    fn seven_card_fast_path() {
+        use rand::{rngs::StdRng, SeedableRng};
+
+        let mut rng = StdRng::seed_from_u64(85);
+
+        for _ in 0..20_000 {
+            let cards = random_cards(7, &mut rng);
+
+            let scanned = cards.iter()
+                .copied()
+                .combinations(5)
+                .map(|cards| Hand::new(cards.try_into().unwrap()))
+                .max()
+                .unwrap();
+
+            let fast = best_hand_of(&cards).unwrap();
+
+            assert_eq!(fast, scanned, "{:?}", cards);
+            assert_eq!(fast.category, scanned.category, "{:?}", cards);
+            assert_eq!(fast.ranks(), scanned.ranks(), "{:?}", cards);
+            assert!(fast.cards.iter().all(|card| cards.contains(card)));
+        }
+    }
+
+    #[test]
+    fn six_card_hands() {
+        // Dropping the two makes the higher straight.
+        let run = [H(Two), D(Three), S(Four), C(Five), H(Six), D(Seven)];
+        let hand = best_hand_of(&run).unwrap();
+
+        assert_eq!(hand.category, Straight);
+        assert_eq!(hand.ranks(), [Seven, Six, Five, Four, Three]);
+        assert!(!hand.cards.contains(&H(Two)));
+
+        // On the turn, the hole cards make the flush over the board's pair.
+        let board = [H(King), S(King), H(Eight), H(Four)];
+        let hand = form_best_hand(&board, &[H(Jack), H(Three)]).unwrap();
+
+        assert_eq!(hand.category, Flush);
+        assert_eq!(hand.ranks(), [King, Jack, Eight, Four, Three]);
+
+        let hand = form_best_hand(&board, &[D(Jack), C(Three)]).unwrap();
+
+        assert_eq!(hand.category, Pair);
+        assert_eq!(hand.ranks(), [King, King, Jack, Eight, Four]);
+    }
+
+    #[test]
+    fn category_before_ranks() {
+        let straight_flush = Hand::new([H(Nine), H(Eight), H(Seven), H(Six), H(Five)]);
+        let straight = Hand::new([S(Nine), D(Eight), H(Seven), C(Six), H(Five)]);
+
+        assert_eq!(straight_flush.ranks(), straight.ranks());
+        assert_ne!(straight_flush, straight);
+        assert!(straight_flush > straight);
+
+        // Flushes of different suits with the same ranks chop.
+        let hearts = Hand::new([H(Ace), H(Jack), H(Eight), H(Four), H(Two)]);
+        let clubs = Hand::new([C(Ace), C(Jack), C(Eight), C(Four), C(Two)]);
+
+        assert_eq!(hearts, clubs);
+        assert_eq!(hearts.cmp(&clubs), std::cmp::Ordering::Equal);
+
+        // The weakest hand of a category still beats the best of the one below.
+        let worst_flush = Hand::new([D(Seven), D(Five), D(Four), D(Three), D(Two)]);
+        let best_straight = Hand::new([S(Ace), D(King), H(Queen), C(Jack), H(Ten)]);
+
+        assert!(worst_flush > best_straight);
+    }
+
+    #[test]
+    fn best_hand_of_any_cards() {
+        let five = [D(Nine), H(Nine), S(Four), C(Jack), H(Two)];
+        let hand = best_hand_of(&five).unwrap();
+
+        assert_eq!(hand.category, Pair);
+        assert!(five.iter().all(|card| hand.cards.contains(card)));
+
+        let seven = [D(Nine), H(Nine), S(Four), C(Jack), H(Two), S(Nine), C(Four)];
+        let hand = best_hand_of(&seven).unwrap();
+
+        assert_eq!(hand.category, FullHouse);
+        assert_eq!(hand.ranks(), [Nine, Nine, Nine, Four, Four]);
+
+        assert!(best_hand_of(&seven[..4]).is_none());
+        assert!(best_hand_of(&[]).is_none());
+    }
+
+    #[test]
+    fn wild_cards() {
+        let cards = [H(Ace), H(King), H(Nine), H(Four), C(Two)];
+        let deuces = [H(Two), C(Two), S(Two), D(Two)];
+
+        assert_eq!(best_hand_with_wild(&cards, &[]).unwrap().category, HighCard);
+
+        let hand = best_hand_with_wild(&cards, &deuces).unwrap();
+
+        assert_eq!(hand.category, Flush);
+        assert_eq!(hand.ranks(), [Ace, King, Queen, Nine, Four]);
+
+        // Two wild cards turn a pair into four of a kind.
+        let cards = [S(Seven), D(Seven), C(Two), S(Two), H(Jack)];
+        let hand = best_hand_with_wild(&cards, &deuces).unwrap();
+
+        assert_eq!(hand.category, FourOfAKind);
+        assert_eq!(hand.ranks(), [Seven, Seven, Seven, Seven, Jack]);
+
+        assert!(best_hand_with_wild(&cards[..4], &deuces).is_none());
+    }
+
+    #[test]
+    fn cloned_hands_are_equal() {
+        let hand = Hand::new([Card::new(Hearts, Ace), S(Ace), C(Seven), D(Seven), H(Two)]);
+        let clone = hand.clone();
+
+        assert_eq!(clone, hand);
+        assert_eq!(clone.cards(), hand.cards());
+        assert_eq!(clone.category(), TwoPair);
+
+        let ranks: Vec<CardRank> = hand.iter().map(|card| card.rank).collect();
+        assert_eq!(ranks, [Ace, Ace, Seven, Seven, Two]);
+
+        assert!(format!("{:?}", hand).contains("TwoPair"));
+    }
+
+    #[cfg(feature = "exhaustive")]
+    #[test]
+    fn textbook_category_counts() {
+        assert_eq!(category_counts(), [
+            1_302_540,
+            1_098_240,
+            123_552,
+            54_912,
+            10_200,
+            5_108,
+            3_744,
+            624,
+            36,
+            4
+        ]);
+    }
+}
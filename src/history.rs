@@ -0,0 +1,224 @@
+use crate::betting::Action;
+use crate::card::Card;
+use crate::game::Street;
+use crate::hand::Hand;
+
+enum Event {
+    Seat { name: String, stack: u32 },
+    Blind { name: String, amount: u32 },
+    Dealt { name: String, hole: [Card; 2] },
+    Street { street: Street, board: Vec<Card>, pot: u32 },
+    Action { name: String, action: Action, chips: u32 },
+    Show { name: String, hole: [Card; 2], description: String },
+    Collect { name: String, amount: u32 }
+}
+
+// Records the course of a single hand and prints it much like a PokerStars
+// hand history. Events are printed in the order they are recorded.
+#[derive(Default)]
+pub struct HandHistory {
+    events: Vec<Event>,
+    // Chips put in so far, across all streets.
+    pot: u32
+}
+
+impl HandHistory {
+    pub fn new() -> HandHistory {
+        HandHistory::default()
+    }
+
+    pub fn seat(&mut self, name: &str, stack: u32) -> &mut HandHistory {
+        self.events.push(Event::Seat { name: name.to_string(), stack });
+        self
+    }
+
+    pub fn blind(&mut self, name: &str, amount: u32) -> &mut HandHistory {
+        self.pot += amount;
+        self.events.push(Event::Blind { name: name.to_string(), amount });
+        self
+    }
+
+    pub fn deal(&mut self, name: &str, hole: [Card; 2]) -> &mut HandHistory {
+        self.events.push(Event::Dealt { name: name.to_string(), hole });
+        self
+    }
+
+    // Starts the betting on a street. 'board' holds all community cards dealt
+    // so far. Preflop betting simply follows the hole cards, so that street
+    // gets no header.
+    pub fn street(&mut self, street: Street, board: &[Card]) -> &mut HandHistory {
+        self.events.push(Event::Street { street, board: board.to_vec(), pot: self.pot });
+        self
+    }
+
+    // Records an action that put 'chips' more chips into the pot.
+    pub fn action(&mut self, name: &str, action: Action, chips: u32) -> &mut HandHistory {
+        self.pot += chips;
+        self.events.push(Event::Action { name: name.to_string(), action, chips });
+        self
+    }
+
+    pub fn show(&mut self, name: &str, hole: [Card; 2], hand: &Hand) -> &mut HandHistory {
+        self.events.push(Event::Show {
+            name: name.to_string(),
+            hole,
+            description: hand.describe()
+        });
+        self
+    }
+
+    pub fn collect(&mut self, name: &str, amount: u32) -> &mut HandHistory {
+        self.events.push(Event::Collect { name: name.to_string(), amount });
+        self
+    }
+
+    pub fn pot(&self) -> u32 {
+        self.pot
+    }
+}
+
+fn write_cards(f: &mut std::fmt::Formatter<'_>, cards: &[Card]) -> std::fmt::Result {
+    write!(f, "[")?;
+
+    for (i, card) in cards.iter().enumerate() {
+        if i > 0 {
+            write!(f, " ")?;
+        }
+
+        write!(f, "{} {}", card.suit, card.rank)?;
+    }
+
+    write!(f, "]")
+}
+
+impl std::fmt::Display for HandHistory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut seat = 0;
+        let mut dealt_header = false;
+        let mut showdown_header = false;
+
+        for event in &self.events {
+            match event {
+                Event::Seat { name, stack } => {
+                    seat += 1;
+                    writeln!(f, "Seat {}: {} ({} in chips)", seat, name, stack)?;
+                }
+                Event::Blind { name, amount } => {
+                    writeln!(f, "{}: posts blind {}", name, amount)?;
+                }
+                Event::Dealt { name, hole } => {
+                    if !dealt_header {
+                        dealt_header = true;
+                        writeln!(f, "*** HOLE CARDS ***")?;
+                    }
+
+                    write!(f, "Dealt to {} ", name)?;
+                    write_cards(f, hole)?;
+                    writeln!(f)?;
+                }
+                Event::Street { street: Street::Preflop, .. } => {}
+                Event::Street { street, board, pot } => {
+                    // The cards of earlier streets go in a separate bracket.
+                    let new = match street {
+                        Street::Turn | Street::River => board.len().saturating_sub(1),
+                        _ => 0
+                    };
+
+                    write!(f, "*** {} *** ", street.to_string().to_uppercase())?;
+
+                    if new > 0 {
+                        write_cards(f, &board[..new])?;
+                        write!(f, " ")?;
+                    }
+
+                    write_cards(f, &board[new..])?;
+                    writeln!(f, " (pot {})", pot)?;
+                }
+                Event::Action { name, action, chips } => {
+                    match action {
+                        Action::Fold      => writeln!(f, "{}: folds", name)?,
+                        Action::Check     => writeln!(f, "{}: checks", name)?,
+                        Action::Call      => writeln!(f, "{}: calls {}", name, chips)?,
+                        Action::Raise(to) => writeln!(f, "{}: raises to {}", name, to)?,
+                        Action::AllIn     => writeln!(f, "{}: bets {} and is all-in", name, chips)?
+                    }
+                }
+                Event::Show { name, hole, description } => {
+                    if !showdown_header {
+                        showdown_header = true;
+                        writeln!(f, "*** SHOWDOWN ***")?;
+                    }
+
+                    write!(f, "{}: shows ", name)?;
+                    write_cards(f, hole)?;
+                    writeln!(f, " ({})", description)?;
+                }
+                Event::Collect { name, amount } => {
+                    writeln!(f, "{} collected {} from pot", name, amount)?;
+                }
+            }
+        }
+
+        writeln!(f, "*** SUMMARY ***")?;
+        write!(f, "Total pot {}", self.pot)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::card::parse_cards;
+    use crate::hand::form_best_hand;
+
+    fn hole(codes: &str) -> [Card; 2] {
+        parse_cards(codes).unwrap().try_into().unwrap()
+    }
+
+    #[test]
+    fn all_in_hand_history() {
+        let alice = hole("Ah As");
+        let bob = hole("Kd Kc");
+        let board = parse_cards("Kh 7s 2d 5c 9h").unwrap();
+
+        let mut history = HandHistory::new();
+
+        history
+            .seat("Alice", 1000)
+            .seat("Bob", 1000)
+            .blind("Alice", 10)
+            .blind("Bob", 20)
+            .deal("Alice", alice)
+            .deal("Bob", bob)
+            .street(Street::Preflop, &[])
+            .action("Alice", Action::AllIn, 990)
+            .action("Bob", Action::Call, 980)
+            .street(Street::Flop, &board[..3])
+            .street(Street::Turn, &board[..4])
+            .street(Street::River, &board)
+            .show("Alice", alice, &form_best_hand(&board, &alice).unwrap())
+            .show("Bob", bob, &form_best_hand(&board, &bob).unwrap());
+
+        let pot = history.pot();
+        history.collect("Bob", pot);
+
+        assert_eq!(history.to_string(), "\
+Seat 1: Alice (1000 in chips)
+Seat 2: Bob (1000 in chips)
+Alice: posts blind 10
+Bob: posts blind 20
+*** HOLE CARDS ***
+Dealt to Alice [♥ A ♠ A]
+Dealt to Bob [♦ K ♣ K]
+Alice: bets 990 and is all-in
+Bob: calls 980
+*** FLOP *** [♥ K ♠ 7 ♦ 2] (pot 2000)
+*** TURN *** [♥ K ♠ 7 ♦ 2] [♣ 5] (pot 2000)
+*** RIVER *** [♥ K ♠ 7 ♦ 2 ♣ 5] [♥ 9] (pot 2000)
+*** SHOWDOWN ***
+Alice: shows [♥ A ♠ A] (Pair of Aces)
+Bob: shows [♦ K ♣ K] (Three of a Kind, Kings)
+Bob collected 2000 from pot
+*** SUMMARY ***
+Total pot 2000");
+    }
+}
@@ -0,0 +1,40 @@
+pub mod card;
+pub mod deck;
+pub mod hand;
+pub mod eval;
+pub mod game;
+pub mod betting;
+pub mod equity;
+pub mod lowball;
+pub mod odds;
+pub mod range;
+pub mod history;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+pub use card::{
+    canonical_key, parse_cards, render_cards, sorted_desc, Card, CardIndexError, CardRank, CardSuit,
+    Color, ParseCardError
+};
+pub use deck::{all_five_card_hands, draw_replace, random_cards, Deck, DrawError};
+pub use eval::{evaluate_7, hand_rank_class, HandRank, HAND_RANK_CLASSES};
+pub use equity::{
+    classify_texture, count_outs, equity_exact, equity_multiway, equity_vs_random, equity_vs_range,
+    unseen_cards, unseen_count, Equity, EquityError, HandState, OutsReport
+};
+#[cfg(feature = "rayon")]
+pub use equity::{par_equity_vs_random, par_equity_vs_range};
+pub use hand::{
+    best_hand_of, best_hand_with_wild, detect_straight, form_best_hand, form_best_hand_omaha,
+    form_best_hand_stud, num_hand_combinations, nut_hand, random_hand,
+    Decider, Hand, HandCategory, HandError, Outcome, Ruleset, Showdown
+};
+#[cfg(feature = "exhaustive")]
+pub use hand::category_counts;
+pub use history::HandHistory;
+pub use lowball::{
+    evaluate_badugi, evaluate_low_ace_to_five, evaluate_low_deuce_to_seven, evaluate_omaha_hi_lo,
+    BadugiHand, LowHand, LowHand27
+};
+pub use odds::{draw_odds, draw_odds_with_unseen, icm_equity, pot_odds, required_equity, shove_ev};
+pub use range::{blockers, parse_range, sample_hand_from_weights, RangeError};
@@ -0,0 +1,410 @@
+use itertools::Itertools;
+
+use crate::card::{Card, CardRank};
+use crate::hand::{form_best_hand_omaha, Hand, HandCategory};
+
+// An ace-to-five low hand. Aces are low and straights and flushes do not
+// count, so the best hand is 5-4-3-2-A. Comparisons are arranged such that
+// the better low hand is the greater one.
+#[derive(Clone, Copy, Debug)]
+pub struct LowHand {
+    // Only the pairing categories are used; any hand with a pair is worse
+    // than every unpaired hand.
+    category: HandCategory,
+    // Ace-low rank values, grouped and ordered like the cards of a 'Hand'.
+    ranks: [u8; 5],
+    cards: [Card; 5]
+}
+
+impl LowHand {
+    pub fn new(cards: [Card; 5]) -> LowHand {
+        let groups: Vec<(usize, u8)> = cards.iter()
+            .map(|card| card.rank.value_ace_low())
+            .sorted()
+            .rev()
+            .dedup_with_count()
+            .sorted_by(|a, b| b.0.cmp(&a.0))
+            .collect();
+
+        let category = match (groups[0].0, groups.get(1).map_or(0, |g| g.0)) {
+            (4, _) => HandCategory::FourOfAKind,
+            (3, 2) => HandCategory::FullHouse,
+            (3, _) => HandCategory::ThreeOfAKind,
+            (2, 2) => HandCategory::TwoPair,
+            (2, _) => HandCategory::Pair,
+            _      => HandCategory::HighCard
+        };
+
+        let mut ranks = [0u8; 5];
+
+        for (i, value) in groups.iter()
+            .flat_map(|&(count, value)| std::iter::repeat_n(value, count))
+            .enumerate()
+        {
+            ranks[i] = value;
+        }
+
+        LowHand { category, ranks, cards }
+    }
+
+    pub fn cards(&self) -> &[Card; 5] {
+        &self.cards
+    }
+
+    // Ace-low rank values from the highest to the lowest card.
+    pub fn ranks(&self) -> &[u8; 5] {
+        &self.ranks
+    }
+
+    pub fn is_unpaired(&self) -> bool {
+        self.category == HandCategory::HighCard
+    }
+}
+
+impl Ord for LowHand {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        // Lower is better.
+        (other.category, other.ranks).cmp(&(self.category, self.ranks))
+    }
+}
+
+impl PartialOrd for LowHand {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for LowHand {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == std::cmp::Ordering::Equal
+    }
+}
+
+impl Eq for LowHand {}
+
+// The best ace-to-five low hand that can be made of any five of 'cards'.
+pub fn evaluate_low_ace_to_five(cards: &[Card]) -> Option<LowHand> {
+    cards.iter()
+        .copied()
+        .combinations(5)
+        .map(|cards| LowHand::new(cards.try_into().unwrap()))
+        .max()
+}
+
+// The high and low halves of an Omaha Hi-Lo hand, made of exactly two of the
+// four hole cards and three of the five community cards each, not necessarily
+// the same ones. Only an unpaired ace-to-five low of eight or better
+// qualifies, so the low half is optional. The whole result is 'None' only
+// for malformed input: a board of other than five cards, or other than four
+// hole cards. A valid hand always has a high half, like 'form_best_hand_omaha'.
+pub fn evaluate_omaha_hi_lo(community: &[Card], hole: &[Card]) -> Option<(Hand, Option<LowHand>)> {
+    let high = form_best_hand_omaha(community, hole)?;
+
+    let low = hole.iter()
+        .copied()
+        .combinations(2)
+        .cartesian_product(community.iter().copied().combinations(3))
+        .map(|(h, c)| {
+            let cards: Vec<Card> = h.into_iter().chain(c).collect();
+            LowHand::new(cards.try_into().unwrap())
+        })
+        .filter(|low| low.is_unpaired() && low.ranks()[0] <= 8)
+        .max();
+
+    Some((high, low))
+}
+
+// A deuce-to-seven low hand. Hands are valued as in high poker, with aces
+// always high, and the worst high hand is the best low, so 7-5-4-3-2 unsuited
+// is the best hand. A-2-3-4-5 is not a straight but a poor ace-high hand.
+// Comparisons are arranged such that the better low hand is the greater one.
+#[derive(Clone, Copy, Debug)]
+pub struct LowHand27 {
+    category: HandCategory,
+    // Ranks in the significance order of a 'Hand'.
+    ranks: [CardRank; 5],
+    cards: [Card; 5]
+}
+
+impl LowHand27 {
+    pub fn new(cards: [Card; 5]) -> LowHand27 {
+        let hand = Hand::new(cards);
+        let mut category = hand.category();
+        let mut ranks = hand.cards().map(|card| card.rank);
+
+        // The ace plays high, so the wheel is no straight.
+        let is_wheel = ranks[0] == CardRank::Five && ranks[4] == CardRank::Ace;
+
+        if is_wheel {
+            category = match category {
+                HandCategory::StraightFlush => HandCategory::Flush,
+                _                           => HandCategory::HighCard
+            };
+
+            ranks.rotate_right(1);
+        }
+
+        LowHand27 { category, ranks, cards }
+    }
+
+    pub fn category(&self) -> HandCategory {
+        self.category
+    }
+
+    pub fn cards(&self) -> &[Card; 5] {
+        &self.cards
+    }
+
+    // Ranks from the most to the least significant, as in a 'Hand'.
+    pub fn ranks(&self) -> &[CardRank; 5] {
+        &self.ranks
+    }
+}
+
+impl Ord for LowHand27 {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        // Lower is better.
+        (other.category, other.ranks).cmp(&(self.category, self.ranks))
+    }
+}
+
+impl PartialOrd for LowHand27 {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for LowHand27 {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == std::cmp::Ordering::Equal
+    }
+}
+
+impl Eq for LowHand27 {}
+
+// The best deuce-to-seven low hand that can be made of any five of 'cards'.
+pub fn evaluate_low_deuce_to_seven(cards: &[Card]) -> Option<LowHand27> {
+    cards.iter()
+        .copied()
+        .combinations(5)
+        .map(|cards| LowHand27::new(cards.try_into().unwrap()))
+        .max()
+}
+
+// A Badugi hand: the largest set of cards with no two sharing a rank or a
+// suit. More cards are better, and among hands of the same size the lower one
+// wins, with aces low. Comparisons are arranged such that the better hand is
+// the greater one.
+#[derive(Clone, Debug)]
+pub struct BadugiHand {
+    // Ace-low rank values, highest first.
+    ranks: Vec<u8>,
+    cards: Vec<Card>
+}
+
+impl BadugiHand {
+    fn new(mut cards: Vec<Card>) -> BadugiHand {
+        cards.sort_by_key(|card| std::cmp::Reverse(card.rank.value_ace_low()));
+
+        let ranks = cards.iter().map(|card| card.rank.value_ace_low()).collect();
+
+        BadugiHand { ranks, cards }
+    }
+
+    // The cards that play, highest first.
+    pub fn cards(&self) -> &[Card] {
+        &self.cards
+    }
+
+    // Ace-low rank values of the cards that play, highest first.
+    pub fn ranks(&self) -> &[u8] {
+        &self.ranks
+    }
+}
+
+impl Ord for BadugiHand {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.cards.len().cmp(&other.cards.len())
+            .then_with(|| other.ranks.cmp(&self.ranks))
+    }
+}
+
+impl PartialOrd for BadugiHand {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for BadugiHand {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == std::cmp::Ordering::Equal
+    }
+}
+
+impl Eq for BadugiHand {}
+
+// The best Badugi hand that can be made of 'cards', normally four of them.
+pub fn evaluate_badugi(cards: &[Card]) -> BadugiHand {
+    let is_badugi = |cards: &Vec<Card>| {
+        cards.iter().map(|card| card.rank).all_unique()
+            && cards.iter().map(|card| card.suit).all_unique()
+    };
+
+    (0..=cards.len().min(4))
+        .flat_map(|n| cards.iter().copied().combinations(n))
+        .filter(is_badugi)
+        .map(BadugiHand::new)
+        .max()
+        .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::card::{CardRank, CardSuit};
+    use CardRank::*;
+    use CardSuit::*;
+
+    fn cards(ranks: &[CardRank]) -> Vec<Card> {
+        [Hearts, Spades, Clubs, Diamonds].into_iter()
+            .cycle()
+            .zip(ranks)
+            .map(|(suit, &rank)| Card { suit, rank })
+            .collect()
+    }
+
+    fn low(ranks: &[CardRank]) -> LowHand {
+        evaluate_low_ace_to_five(&cards(ranks)).unwrap()
+    }
+
+    #[test]
+    fn ace_to_five_ordering() {
+        assert!(low(&[Six, Four, Three, Two, Ace]) > low(&[Six, Five, Four, Three, Two]));
+        assert!(low(&[Seven, Six, Four, Three, Two]) > low(&[Seven, Six, Five, Three, Two]));
+
+        // Any unpaired hand beats a paired one.
+        assert!(low(&[King, Queen, Jack, Nine, Eight]) > low(&[Ace, Ace, Two, Three, Four]));
+        assert!(low(&[Two, Two, Three, Four, Five]) > low(&[Two, Two, Three, Three, Four]));
+    }
+
+    #[test]
+    fn wheel_is_the_nuts() {
+        let wheel = low(&[Five, Four, Three, Two, Ace]);
+
+        assert!(wheel.is_unpaired());
+        assert_eq!(wheel.ranks(), &[5, 4, 3, 2, 1]);
+
+        // Suits do not matter, so a suited wheel is just as good.
+        let suited = evaluate_low_ace_to_five(&[
+            Card { suit: Hearts, rank: Ace },
+            Card { suit: Hearts, rank: Two },
+            Card { suit: Hearts, rank: Three },
+            Card { suit: Hearts, rank: Four },
+            Card { suit: Hearts, rank: Five }
+        ]).unwrap();
+
+        assert_eq!(wheel, suited);
+
+        // The best five of seven cards.
+        assert_eq!(low(&[King, Five, Four, Queen, Three, Two, Ace]), wheel);
+        assert!(low(&[Six, Four, Three, Two, Ace]) < wheel);
+
+        assert!(evaluate_low_ace_to_five(&cards(&[Ace, Two, Three, Four])).is_none());
+    }
+
+    fn low27(ranks: &[CardRank]) -> LowHand27 {
+        evaluate_low_deuce_to_seven(&cards(ranks)).unwrap()
+    }
+
+    #[test]
+    fn deuce_to_seven_ordering() {
+        let nuts = low27(&[Seven, Five, Four, Three, Two]);
+
+        assert!(nuts > low27(&[Seven, Six, Four, Three, Two]));
+        assert!(nuts > low27(&[Eight, Five, Four, Three, Two]));
+
+        // Straights count against the hand, and aces are high.
+        assert!(low27(&[Eight, Six, Four, Three, Two]) > low27(&[Six, Five, Four, Three, Two]));
+        assert!(nuts > low27(&[Five, Four, Three, Two, Ace]));
+
+        let wheel = low27(&[Five, Four, Three, Two, Ace]);
+
+        assert_eq!(wheel.category(), HandCategory::HighCard);
+        assert_eq!(wheel.ranks(), &[Ace, Five, Four, Three, Two]);
+        assert!(low27(&[King, Queen, Jack, Nine, Seven]) > wheel);
+
+        // The best five of seven cards.
+        assert_eq!(low27(&[King, Seven, Five, Queen, Four, Three, Two]), nuts);
+
+        assert!(evaluate_low_deuce_to_seven(&cards(&[Seven, Five, Four, Three])).is_none());
+    }
+
+    #[test]
+    fn deuce_to_seven_flushes() {
+        let flush = |ranks: &[CardRank]| {
+            let cards: Vec<Card> = ranks.iter().map(|&rank| Card { suit: Hearts, rank }).collect();
+            evaluate_low_deuce_to_seven(&cards).unwrap()
+        };
+
+        let suited_nuts = flush(&[Seven, Five, Four, Three, Two]);
+
+        assert_eq!(suited_nuts.category(), HandCategory::Flush);
+
+        // Even the worst unpaired hand or a straight beats a flush.
+        assert!(low27(&[Ace, King, Queen, Jack, Nine]) > suited_nuts);
+        assert!(low27(&[Ace, King, Queen, Jack, Ten]) > suited_nuts);
+        assert!(suited_nuts > low27(&[Two, Two, Two, Three, Three]));
+
+        // A suited wheel is a flush, not a straight flush.
+        assert_eq!(flush(&[Five, Four, Three, Two, Ace]).category(), HandCategory::Flush);
+    }
+
+    #[test]
+    fn badugi_hands() {
+        let badugi = |codes: &str| evaluate_badugi(&crate::card::parse_cards(codes).unwrap());
+
+        let nuts = badugi("Ah 2s 3c 4d");
+
+        assert_eq!(nuts.cards().len(), 4);
+        assert_eq!(nuts.ranks(), &[4, 3, 2, 1]);
+        assert!(nuts > badugi("Ah 2s 3c 5d"));
+        assert!(badugi("Kh Qs Jc Td") > badugi("Ah 2s 3c 3d"));
+
+        // A pair or a repeated suit leaves a three-card hand.
+        let paired = badugi("Ah 2s 3c 3d");
+        let suited = badugi("Ah 2h 3c 4d");
+
+        assert_eq!(paired.ranks(), &[3, 2, 1]);
+        assert_eq!(suited.ranks(), &[4, 3, 1]);
+        assert!(paired > suited);
+
+        assert_eq!(badugi("Ah 2h 3h 4h").ranks(), &[1]);
+        assert!(badugi("").cards().is_empty());
+    }
+
+    #[test]
+    fn omaha_hi_lo() {
+        let parse = |codes: &str| crate::card::parse_cards(codes).unwrap();
+
+        let board = parse("2h 5h 6c Kh 9s");
+        let (high, low) = evaluate_omaha_hi_lo(&board, &parse("Ah 3h 4d 7c")).unwrap();
+
+        assert_eq!(high.category(), HandCategory::Flush);
+        assert_eq!(low.unwrap().ranks(), &[6, 5, 3, 2, 1]);
+
+        // Only two low cards on the board leave no qualifying low.
+        let board = parse("2h 5h Qc Kh 9s");
+        let (_, low) = evaluate_omaha_hi_lo(&board, &parse("Ah 3h 4d 7c")).unwrap();
+
+        assert!(low.is_none());
+
+        // A nine-low does not qualify.
+        let board = parse("2h 5h 9c Kh Qs");
+        let (_, low) = evaluate_omaha_hi_lo(&board, &parse("Ah 3h Jd Tc")).unwrap();
+
+        assert!(low.is_none());
+
+        assert!(evaluate_omaha_hi_lo(&board[..4], &parse("Ah 3h Jd Tc")).is_none());
+        assert!(evaluate_omaha_hi_lo(&board, &parse("Ah 3h Jd")).is_none());
+    }
+}
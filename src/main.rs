@@ -1,501 +1,202 @@
 use itertools::Itertools;
+use rand::{rngs::StdRng, SeedableRng};
 
-struct Player {
-    name: String,
-    money: u32,
-    hole_cards: Vec<Card>
-}
+use poker::game::{Chop, Player, PlayerId, Round};
+use poker::{equity_exact, equity_vs_random, form_best_hand, Card, Deck, Equity, Hand};
 
-impl Player {
-    pub fn is_busted(&self) -> bool {
-        self.money == 0
-    }
-}
+// Plays out a single hand of Texas Hold'em as a bomb pot, with antes but no
+// betting.
+//
+//     poker [players] [seed]
+//
+// There are 4 players by default, and a random seed.
 
-#[derive(Eq, PartialEq, Clone, Copy, Debug)]
-enum CardSuit {
-    Spades,
-    Hearts,
-    Clubs,
-    Diamonds
-}
+// What every player puts into the bomb pot.
+const ANTE: u32 = 25;
 
-impl std::fmt::Display for CardSuit {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        use CardSuit::*;
+// The chips every player sits down with.
+const STACK: u32 = 1000;
 
-        match self {
-            Spades   => '♠',
-            Hearts   => '♥',
-            Clubs    => '♣',
-            Diamonds => '♦'
-        }.fmt(f)
-    }
-}
+fn main() {
+    let mut args = std::env::args().skip(1);
 
-#[derive(PartialEq, Eq, PartialOrd, Ord, Debug, Clone, Copy)]
-enum CardRank {
-    Two,
-    Three,
-    Four,
-    Five,
-    Six,
-    Seven,
-    Eight,
-    Nine,
-    Ten,
-    Jack,
-    Queen,
-    King,
-    Ace
-}
+    let players: usize = match args.next().map(|arg| arg.parse()) {
+        None => 4,
+        Some(Ok(n)) if (2..=10).contains(&n) => n,
+        Some(_) => exit_with("the number of players must be from 2 to 10")
+    };
 
-impl std::fmt::Display for CardRank {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        use CardRank::*;
-
-        match self {
-            Two   => "2",
-            Three => "3",
-            Four  => "4",
-            Five  => "5",
-            Six   => "6",
-            Seven => "7",
-            Eight => "8",
-            Nine  => "9",
-            Ten   => "10",
-            Jack  => "J",
-            Queen => "Q",
-            King  => "K",
-            Ace   => "A"
-        }.fmt(f)
-    }
+    let seed: u64 = match args.next().map(|arg| arg.parse()) {
+        None => rand::random(),
+        Some(Ok(seed)) => seed,
+        Some(Err(_)) => exit_with("the seed must be a non-negative integer")
+    };
+
+    play(players, seed);
 }
 
-#[derive(Clone, Copy, Debug)]
-struct Card {
-    suit: CardSuit,
-    rank: CardRank
+fn exit_with(message: &str) -> ! {
+    eprintln!("{}", message);
+    std::process::exit(2);
 }
 
-impl std::fmt::Display for Card {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "[{} {: >2}]", self.suit, self.rank)
+fn print_cards(label: &str, cards: &[Card]) {
+    print!("{}:", label);
+
+    for card in cards {
+        print!(" {}", card);
     }
+
+    println!();
 }
 
-impl Ord for Card {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        self.rank.cmp(&other.rank)
+// The equity of 'hero' against a single random hand. With only the river to
+// come every hand the opponent might hold is played out; earlier, a
+// simulation with a fixed seed keeps the output of a seeded game reproducible.
+fn live_odds(hero: [Card; 2], board: &[Card]) -> Equity {
+    if board.len() < 4 {
+        let mut rng = StdRng::seed_from_u64(0);
+        return equity_vs_random(hero, board, 20_000, &mut rng);
     }
-}
 
-impl PartialOrd for Card {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(self.cmp(other))
+    let known: Vec<Card> = hero.iter().chain(board).copied().collect();
+    let mut total = Equity::default();
+    let mut opponents = 0;
+
+    // Each opponent hand leaves the same number of rivers, so they all weigh
+    // the same.
+    for (a, b) in Deck::without(&known).cards().iter().copied().tuple_combinations() {
+        let equity = equity_exact(hero, [a, b], board).unwrap();
+
+        total.win += equity.win;
+        total.tie += equity.tie;
+        total.loss += equity.loss;
+        opponents += 1;
     }
-}
 
-impl PartialEq for Card {
-    fn eq(&self, other: &Self) -> bool {
-        self.rank == other.rank
+    Equity {
+        win: total.win / opponents as f64,
+        tie: total.tie / opponents as f64,
+        loss: total.loss / opponents as f64
     }
 }
 
-impl Eq for Card {}
+fn print_live_odds(hero: [Card; 2], board: &[Card]) {
+    let equity = live_odds(hero, board);
 
-struct Deck {
-    cards: Vec<Card>
+    println!(
+        "Odds: win {:.1}%, tie {:.1}%, loss {:.1}%",
+        equity.win * 100.0,
+        equity.tie * 100.0,
+        equity.loss * 100.0
+    );
 }
 
-impl Deck {
-    pub fn empty() -> Deck {
-        Deck { cards: Vec::with_capacity(52) }
-    }
+fn play(players: usize, seed: u64) {
+    println!("Seed {}", seed);
 
-    pub fn generate() -> Deck {
-        let mut deck = Deck::empty();
-        deck.regenerate();
-        deck
-    }
+    // The dealer is the last player, so player 1 is dealt to first.
+    let mut seated: Vec<Player> = (1..=players as u32)
+        .map(|n| Player::new(PlayerId(n), &format!("Player {}", n), STACK))
+        .collect();
 
-    pub fn regenerate(&mut self) {
-        let all_ranks = [
-            CardRank::Two,
-            CardRank::Three,
-            CardRank::Four,
-            CardRank::Five,
-            CardRank::Six,
-            CardRank::Seven,
-            CardRank::Eight,
-            CardRank::Nine,
-            CardRank::Ten,
-            CardRank::Jack,
-            CardRank::Queen,
-            CardRank::King,
-            CardRank::Ace
-        ];
-
-        let all_suits = [
-            CardSuit::Hearts,
-            CardSuit::Spades, 
-            CardSuit::Clubs, 
-            CardSuit::Diamonds
-        ];
-
-        self.cards.clear();
-
-        for suit in all_suits {
-            for rank in all_ranks {
-                self.cards.push(Card { suit, rank });
-            }
-        }
+    seated.rotate_right(1);
 
-        self.shuffle();
-    }
+    let mut round = Round::new_bomb(Deck::shuffled_from_seed(seed), ANTE, &mut seated);
+    round.deal_hole_cards(&mut seated).unwrap();
+    round.players = seated;
 
-    pub fn shuffle(&mut self) {
-        use rand::seq::SliceRandom;
-        self.cards.shuffle(&mut rand::thread_rng());
-    }
+    // The players from the left of the dealer round to the dealer.
+    let in_turn: Vec<usize> = (1..=players).map(|seat| seat % players).collect();
 
-    pub fn draw(&mut self) -> Option<Card> {
-        self.cards.pop()
+    for &seat in &in_turn {
+        let player = &round.players[seat];
+        print_cards(&player.name, &player.hole_cards);
     }
-}
 
-struct Pot<'a> {
-    size: u32,
-    players: Vec<&'a Player>
-}
+    // The odds are shown from the first player's point of view.
+    let first = &round.players[1].hole_cards;
+    let hero = [first[0], first[1]];
 
-struct Round<'a> {
-    deck: Deck,
-    community_cards: Vec<Card>,
-    main_pot: Pot<'a>,
-    side_pots: Vec<Pot<'a>>
-}
+    round.deal_flop().unwrap();
+    print_cards("Flop", &round.community_cards);
+    print_live_odds(hero, &round.community_cards);
 
-struct Tournament {
-    blinds: (u32, u32),
-    players: Vec<Box<Player>>,
-    dealer: u8
-}
+    round.deal_turn().unwrap();
+    print_cards("Turn", &round.community_cards);
+    print_live_odds(hero, &round.community_cards);
 
-#[derive(PartialEq, Eq, PartialOrd, Ord, Debug)]
-enum HandCategory {
-    HighCard,
-    Pair,
-    TwoPair,
-    ThreeOfAKind,
-    Straight,
-    Flush,
-    FullHouse,
-    FourOfAKind,
-    StraightFlush,
-    RoyalFlush
-}
+    round.deal_river().unwrap();
+    print_cards("River", &round.community_cards);
 
-#[derive(PartialEq, Eq, PartialOrd, Ord)]
-struct Hand {
-    category: HandCategory,
-    // The order of 'cards' is significant in comparing the ranks of two hands.
-    // The card(s) that define the hand category come first and in descending
-    // rank order. If there are one or more kickers, they follow the
-    // aforementioned cards in descending rank order.
-    // The sorting method ranks ace lowest if doing so results
-    // in a stronger hand (e.g. a "five-high straight" over an "ace high").
-    cards: [Card; 5]
-}
+    let hands: Vec<(&Player, Hand)> = in_turn.iter()
+        .map(|&seat| &round.players[seat])
+        .map(|player| (player, form_best_hand(&round.community_cards, &player.hole_cards).unwrap()))
+        .collect();
 
-impl Hand {
-    pub fn new(mut cards: [Card; 5]) -> Hand {
-        Hand {
-            category: Self::sort_and_categorize(&mut cards),
-            cards: cards
-        }
+    for (player, hand) in &hands {
+        println!("{} has {}", player.name, hand);
     }
 
-    fn sort_and_categorize(cards: &mut [Card; 5]) -> HandCategory {
-        cards.sort();
-        cards.reverse();
-
-        // All cards have same suit.
-        let is_flush = cards.iter()
-            .all(|card| card.suit == cards[0].suit);
-
-        let is_straight = {
-            let mut sub = [0i8; 4];
-
-            // Compare the 4 highest cards to the lowest ranking card.
-            for i in 0..sub.len() {
-                sub[i] = cards[i].rank as i8 - cards[4].rank as i8;
-            }
-
-            if sub.eq(&[4, 3, 2, 1]) {
-                true
-            }
-            else if sub.eq(&[12, 3, 2, 1]) {
-                // Five-high straight.
-                cards.rotate_left(1);
-                true
-            }
-            else {
-                false
-            }
-        };
-
-        if is_flush {
-            let is_royal = cards.iter()
-                .all(|card| card.rank >= CardRank::Ten);
-
-            if is_royal {
-                HandCategory::RoyalFlush
-            }
-            else if is_straight {
-                HandCategory::StraightFlush
-            }
-            else {
-                HandCategory::Flush
-            }
-        }
-        else if is_straight {
-            HandCategory::Straight
-        }
-        else {
-            let mut t: Vec<Vec<Card>> = Vec::with_capacity(5);
-
-            // Group cards by rank.
-            for (_, group) in &(*cards).into_iter().group_by(|card| card.rank) {
-                t.push(group.collect());
-            }
-
-            // Sort by group length and then card rank.
-            t.sort_by(|a, b| match b.len().cmp(&a.len()) {
-                std::cmp::Ordering::Equal => b[0].rank.cmp(&a[0].rank),
-                o => o
-            });
-
-            // Copy the order.
-            for (i, &card) in t.iter().flatten().enumerate() {
-                cards[i] = card;
-            }
-
-            match t[0].len() {
-                4 => HandCategory::FourOfAKind,
-
-                3 => match t[1].len() {
-                    2 => HandCategory::FullHouse,
-                    _ => HandCategory::ThreeOfAKind
-                },
-
-                2 => match t[1].len() {
-                    2 => HandCategory::TwoPair,
-                    _ => HandCategory::Pair
-                },
-
-                _ => HandCategory::HighCard
-            }
+    let best = hands.iter().map(|(_, hand)| hand).max().unwrap();
+
+    let winners: Vec<&Player> = hands.iter()
+        .filter(|(_, hand)| hand == best)
+        .map(|&(player, _)| player)
+        .collect();
+
+    match winners.as_slice() {
+        [winner] => println!("{} wins with {}", winner.name, best.describe()),
+        _        => {
+            let numbers: Vec<String> = winners.iter().map(|player| player.id.0.to_string()).collect();
+
+            println!("Players {} tie with {}", numbers.join(", "), best.describe());
         }
     }
-}
 
-fn form_best_hand(community: &[Card], hole: &[Card]) -> Option<Hand>
-{
-    let mut hands: Vec<Hand> = Vec::new();
-
-    for h in hole.iter().copied().combinations(2) {
-        hands.push(
-            community
-            .iter()
-            .copied()
-            .chain(h.into_iter())
-            .combinations(5)
-            .map(|cards| Hand::new(cards.try_into().unwrap()))
-            .max()
-            .unwrap()
-        );
+    for chop in &round.award_pots().chops {
+        print_chop(chop);
     }
-
-    hands.into_iter().max()
 }
 
-#[cfg(test)]
-mod tests {
-    use crate::{Card, CardRank, CardSuit, HandCategory, Hand};
-    use CardRank::*;
-    use CardSuit::*;
-    use HandCategory::*;
-
-    #[allow(non_snake_case)]
-    const fn H(rank: CardRank) -> Card {
-        Card { suit: Hearts, rank }
-    }
+fn print_chop(chop: &Chop) {
+    let listed: Vec<String> = chop.shares.iter()
+        .map(|&(id, share)| format!("Player {} gets {}", id.0, share))
+        .collect();
 
-    #[allow(non_snake_case)]
-    const fn C(rank: CardRank) -> Card {
-        Card { suit: Clubs, rank }
-    }
+    print!("The pot of {} is chopped: {}", chop.pot, listed.join(", "));
 
-    #[allow(non_snake_case)]
-    const fn S(rank: CardRank) -> Card {
-        Card { suit: Spades, rank }
-    }
+    let odd_chips = (chop.pot % chop.shares.len() as u32) as usize;
 
-    #[allow(non_snake_case)]
-    const fn D(rank: CardRank) -> Card {
-        Card { suit: Diamonds, rank }
-    }
+    match odd_chips {
+        0 => {}
+        1 => print!(" (odd chip to Player {})", chop.shares[0].0.0),
+        _ => {
+            let lucky: Vec<String> = chop.shares[..odd_chips].iter()
+                .map(|(id, _)| id.0.to_string())
+                .collect();
 
-    #[test]
-    fn hand_categorization() {
-
-        let hand = Hand::new([
-            H(Jack),
-            H(Ten),
-            H(Ace),
-            H(King),
-            H(Queen),
-        ]);
-
-        assert_eq!(hand.category, RoyalFlush);
-
-        let hand = Hand::new([
-            H(Three),
-            H(Four),
-            H(Five),
-            H(Six),
-            H(Seven),
-        ]);
-
-        assert_eq!(hand.category, StraightFlush);
-
-        let hand = Hand::new([
-            H(Three),
-            H(Two),
-            H(Five),
-            H(Ace),
-            H(Seven),
-        ]);
-
-        assert_eq!(hand.category, Flush);
-
-        let hand = Hand::new([
-            D(Two),
-            H(Jack),
-            C(Two),
-            S(Two),
-            H(Two),
-        ]);
-
-        assert_eq!(hand.category, FourOfAKind);
-
-        // Five-high straight.
-        let hand = Hand::new([
-            H(Ace),
-            C(Four),
-            S(Five),
-            H(Three),
-            H(Two),
-        ]);
-
-        assert_eq!(hand.category, Straight);
-
-        // Ace-high straight.
-        let hand = Hand::new([
-            H(Jack),
-            H(Ten),
-            H(Ace),
-            S(Queen),
-            C(King),
-        ]);
-
-        assert_eq!(hand.category, Straight);
-
-        // Eight-high straight.
-        let hand = Hand::new([
-            H(Eight),
-            S(Six),
-            H(Five),
-            H(Four),
-            C(Seven),
-        ]);
-
-        assert_eq!(hand.category, Straight);
-
-        let hand = Hand::new([
-            H(Three),
-            D(Four),
-            S(Seven),
-            C(Seven),
-            H(Seven),
-        ]);
-
-        assert_eq!(hand.category, ThreeOfAKind);
-
-        let hand = Hand::new([
-            H(Four),
-            D(Four),
-            S(Seven),
-            C(Seven),
-            H(Seven),
-        ]);
-
-        assert_eq!(hand.category, FullHouse);
-
-        let hand = Hand::new([
-            H(Four),
-            D(Five),
-            S(Five),
-            C(Jack),
-            H(Jack),
-        ]);
-
-        assert_eq!(hand.category, TwoPair);
-
-        let hand = Hand::new([
-            H(Four),
-            D(Five),
-            S(Nine),
-            C(Jack),
-            H(Jack),
-        ]);
-
-        assert_eq!(hand.category, Pair);
-
-        let hand = Hand::new([
-            H(Four),
-            D(Five),
-            S(Nine),
-            C(Jack),
-            H(Two),
-        ]);
-
-        assert_eq!(hand.category, HighCard);
+            print!(" (odd chips to Players {})", lucky.join(", "));
+        }
     }
 
+    println!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
     #[test]
-    fn hand_comparison() {
-        let king_high = Hand::new([
-            H(Four),
-            D(Five),
-            S(Three),
-            C(King),
-            H(Two),
-        ]);
-
-        let jack_high = Hand::new([
-            H(Four),
-            D(Five),
-            S(Nine),
-            C(Jack),
-            H(Two),
-        ]);
-
-        assert!(jack_high < king_high);
-    }
+    fn live_odds_on_the_flop_and_turn() {
+        let cards = poker::parse_cards("Ah Kh Qh 7h 2c 3d").unwrap();
+        let hero = [cards[0], cards[1]];
 
-}
+        for board in [&cards[2..5], &cards[2..6]] {
+            let equity = live_odds(hero, board);
 
-fn main() {
+            assert!((equity.win + equity.tie + equity.loss - 1.0).abs() < 1e-9, "{:?}", equity);
+            assert!(equity.win > 0.5, "{:?}", equity);
+        }
+    }
 }
@@ -0,0 +1,192 @@
+// The fraction of the final pot, after calling, that the call makes up.
+// 'pot' includes every bet made so far, including the one being called.
+pub fn pot_odds(to_call: u32, pot: u32) -> f64 {
+    if to_call == 0 {
+        return 0.0;
+    }
+
+    to_call as f64 / (pot as u64 + to_call as u64) as f64
+}
+
+// The probability of winning at which calling breaks even. A call risks
+// 'to_call' to win what is already in the pot, so it equals the pot odds.
+pub fn required_equity(to_call: u32, pot: u32) -> f64 {
+    pot_odds(to_call, pot)
+}
+
+// The probability that at least one of 'outs' arrives in Hold'em within
+// 'cards_to_come' of the five board cards. Only the hole cards and the board
+// so far are seen, which leaves 52 - 2 - (5 - cards_to_come) unseen cards:
+// 47 after the flop and 46 after the turn.
+pub fn draw_odds(outs: u32, cards_to_come: u32) -> f64 {
+    let unseen = (52 - 2 - 5 + cards_to_come).min(52 - 2);
+
+    draw_odds_with_unseen(outs, cards_to_come, unseen)
+}
+
+// Like 'draw_odds', with 'unseen' cards left to be dealt from. The chance of
+// missing every time is that of drawing all cards from the non-outs.
+pub fn draw_odds_with_unseen(outs: u32, cards_to_come: u32, unseen: u32) -> f64 {
+    if outs == 0 || cards_to_come == 0 {
+        return 0.0;
+    }
+
+    let outs = outs.min(unseen);
+    let blanks = unseen - outs;
+
+    if cards_to_come > blanks {
+        return 1.0;
+    }
+
+    let miss: f64 = (0..cards_to_come)
+        .map(|i| (blanks - i) as f64 / (unseen - i) as f64)
+        .product();
+
+    1.0 - miss
+}
+
+// The expected chip gain of going all-in for 'effective_stack' into 'pot',
+// compared to folding. Either the opponent folds and the pot is won as is, or
+// they call and the hero wins 'hero_equity' of the pot with both stacks in it,
+// having put in 'effective_stack' themselves.
+pub fn shove_ev(hero_equity: f64, fold_probability: f64, pot: u32, effective_stack: u32) -> f64 {
+    let pot = pot as f64;
+    let stack = effective_stack as f64;
+
+    let called = hero_equity * (pot + 2.0 * stack) - stack;
+
+    fold_probability * pot + (1.0 - fold_probability) * called
+}
+
+// Expected prize money of each player under the Independent Chip Model.
+// The chance of a player finishing in the next open place is their share of
+// the chips of the players not yet placed ("Malmuth-Harville"). Players
+// without chips tie for the last places and split their payouts evenly.
+pub fn icm_equity(stacks: &[u32], payouts: &[u32]) -> Vec<f64> {
+    fn place(
+        stacks: &[u32],
+        payouts: &[u32],
+        remaining: &mut [bool],
+        probability: f64,
+        equity: &mut [f64]
+    ) {
+        let Some((&payout, rest)) = payouts.split_first() else {
+            return;
+        };
+
+        let total: u64 = stacks.iter()
+            .zip(remaining.iter())
+            .filter(|(_, &left)| left)
+            .map(|(&stack, _)| stack as u64)
+            .sum();
+
+        if total == 0 {
+            let left = remaining.iter().filter(|&&left| left).count();
+            let share = probability * payouts.iter().sum::<u32>() as f64 / left as f64;
+
+            for (e, _) in equity.iter_mut().zip(remaining.iter()).filter(|(_, &left)| left) {
+                *e += share;
+            }
+
+            return;
+        }
+
+        for i in 0..stacks.len() {
+            if !remaining[i] || stacks[i] == 0 {
+                continue;
+            }
+
+            let p = probability * stacks[i] as f64 / total as f64;
+            equity[i] += p * payout as f64;
+
+            remaining[i] = false;
+            place(stacks, rest, remaining, p, equity);
+            remaining[i] = true;
+        }
+    }
+
+    let mut equity = vec![0.0; stacks.len()];
+    let payouts = &payouts[..payouts.len().min(stacks.len())];
+
+    place(stacks, payouts, &mut vec![true; stacks.len()], 1.0, &mut equity);
+
+    equity
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn completing_draws() {
+        // A flush draw on the flop: 1 - 38/47 * 37/46.
+        assert!((draw_odds(9, 2) - 0.3497).abs() < 1e-4);
+        assert!((draw_odds(9, 1) - 9.0 / 46.0).abs() < 1e-12);
+        assert!((draw_odds(8, 2) - 0.3145).abs() < 1e-4);
+
+        assert_eq!(draw_odds(9, 0), 0.0);
+        assert_eq!(draw_odds(0, 2), 0.0);
+        assert_eq!(draw_odds_with_unseen(10, 2, 10), 1.0);
+        assert_eq!(draw_odds_with_unseen(3, 2, 4), 1.0);
+
+        // Without outs nothing can come, however few blanks are left.
+        assert_eq!(draw_odds(0, 3), 0.0);
+        assert_eq!(draw_odds_with_unseen(0, 3, 2), 0.0);
+        assert_eq!(draw_odds_with_unseen(0, 1, 0), 0.0);
+
+        // Preflop, all five board cards are to come from 50.
+        assert!((draw_odds(2, 5) - (1.0 - 45.0 * 44.0 / (50.0 * 49.0))).abs() < 1e-12);
+    }
+
+    #[test]
+    fn all_in_ev() {
+        // Folds half the time win the 30 in the pot. When called, 45% of the
+        // 230 chip pot is worth 103.5 for the 100 put in.
+        assert!((shove_ev(0.45, 0.5, 30, 100) - (15.0 + 0.5 * 3.5)).abs() < 1e-9);
+
+        assert!((shove_ev(0.3, 0.0, 30, 100) - -31.0).abs() < 1e-9);
+        assert_eq!(shove_ev(0.0, 1.0, 30, 100), 30.0);
+    }
+
+    #[test]
+    fn calling_requirements() {
+        assert_eq!(pot_odds(50, 150), 0.25);
+        assert_eq!(required_equity(50, 150), 0.25);
+        assert_eq!(required_equity(0, 150), 0.0);
+        assert_eq!(required_equity(0, 0), 0.0);
+        assert_eq!(required_equity(u32::MAX, u32::MAX), 0.5);
+    }
+
+    #[test]
+    fn icm_three_players() {
+        let equity = icm_equity(&[5000, 3000, 2000], &[50, 30, 20]);
+        let expected = [38.393, 32.75, 28.857];
+
+        for (e, x) in equity.iter().zip(expected) {
+            assert!((e - x).abs() < 0.001, "{:?}", equity);
+        }
+
+        assert!((equity.iter().sum::<f64>() - 100.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn icm_degenerate_cases() {
+        // With a single payout ICM is proportional to chips.
+        assert_eq!(icm_equity(&[750, 250], &[100]), vec![75.0, 25.0]);
+
+        let equity = icm_equity(&[1000, 1000, 1000, 1000], &[60, 40]);
+
+        assert!(equity.iter().all(|&e| (e - 25.0).abs() < 1e-9), "{:?}", equity);
+
+        // More payouts than players left.
+        assert_eq!(icm_equity(&[100], &[60, 40]), vec![60.0]);
+
+        // Busted players share the places left over.
+        assert_eq!(icm_equity(&[1000, 0], &[60, 40]), vec![60.0, 40.0]);
+
+        let equity = icm_equity(&[1000, 0, 0], &[50, 30, 20]);
+
+        assert_eq!(equity, vec![50.0, 25.0, 25.0]);
+        assert!((icm_equity(&[300, 0, 700], &[70, 30]).iter().sum::<f64>() - 100.0).abs() < 1e-9);
+    }
+}
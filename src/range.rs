@@ -0,0 +1,345 @@
+use itertools::Itertools;
+use rand::{Rng, RngCore};
+
+use crate::card::{Card, CardRank, CardSuit};
+
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum RangeError {
+    // The token is not a starting hand such as "AA", "AKs", "AKo" or "AK",
+    // optionally followed by '+'.
+    BadToken(String),
+    // The two ends of a span such as "A2s-A5s" must both be pairs, or share the
+    // high card and suitedness.
+    BadSpan(String)
+}
+
+impl std::fmt::Display for RangeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RangeError::BadToken(token) => write!(f, "\"{}\" is not a starting hand", token),
+            RangeError::BadSpan(token) => write!(f, "\"{}\" is not a valid span of hands", token)
+        }
+    }
+}
+
+impl std::error::Error for RangeError {}
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+enum Suitedness {
+    Suited,
+    Offsuit,
+    Any
+}
+
+// A starting hand without suits, e.g. "AKs". The high rank is never lower than
+// the low rank, and pairs always have 'Suitedness::Any'.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+struct HandClass {
+    high: CardRank,
+    low: CardRank,
+    suitedness: Suitedness
+}
+
+impl HandClass {
+    fn is_pair(&self) -> bool {
+        self.high == self.low
+    }
+
+    fn with_ranks(&self, high: CardRank, low: CardRank) -> HandClass {
+        HandClass { high, low, ..*self }
+    }
+
+    // The concrete two-card combinations, higher card first.
+    fn combos(&self) -> Vec<[Card; 2]> {
+        let suits = CardSuit::all();
+
+        if self.is_pair() {
+            return suits.into_iter()
+                .tuple_combinations()
+                .map(|(a, b)| [Card { suit: a, rank: self.high }, Card { suit: b, rank: self.low }])
+                .collect();
+        }
+
+        suits.into_iter()
+            .cartesian_product(suits)
+            .filter(|(a, b)| match self.suitedness {
+                Suitedness::Suited  => a == b,
+                Suitedness::Offsuit => a != b,
+                Suitedness::Any     => true
+            })
+            .map(|(a, b)| [Card { suit: a, rank: self.high }, Card { suit: b, rank: self.low }])
+            .collect()
+    }
+}
+
+impl std::str::FromStr for HandClass {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let chars: Vec<char> = s.chars().collect();
+
+        if !(2..=3).contains(&chars.len()) {
+            return Err(());
+        }
+
+        let rank = |c: char| c.to_ascii_uppercase().to_string().parse::<CardRank>().map_err(|_| ());
+
+        let (a, b) = (rank(chars[0])?, rank(chars[1])?);
+
+        let suitedness = match chars.get(2) {
+            None             => Suitedness::Any,
+            Some('s' | 'S')  => Suitedness::Suited,
+            Some('o' | 'O')  => Suitedness::Offsuit,
+            Some(_)          => return Err(())
+        };
+
+        if a == b && suitedness != Suitedness::Any {
+            return Err(());
+        }
+
+        Ok(HandClass { high: a.max(b), low: a.min(b), suitedness })
+    }
+}
+
+// Ranks from 'low' to 'high', both included.
+fn ranks_between(low: CardRank, high: CardRank) -> impl Iterator<Item = CardRank> {
+    CardRank::all().into_iter().filter(move |rank| (low..=high).contains(rank))
+}
+
+// Expands one token of a range into its hand classes.
+fn parse_token(token: &str) -> Result<Vec<HandClass>, RangeError> {
+    let bad_token = || RangeError::BadToken(token.to_string());
+
+    if let Some(base) = token.strip_suffix('+') {
+        let class: HandClass = base.parse().map_err(|_| bad_token())?;
+
+        // "99+" climbs to aces, "ATs+" climbs the kicker up to "AKs".
+        return Ok(if class.is_pair() {
+            ranks_between(class.low, CardRank::Ace)
+                .map(|rank| class.with_ranks(rank, rank))
+                .collect()
+        }
+        else {
+            ranks_between(class.low, class.high)
+                .filter(|&rank| rank != class.high)
+                .map(|rank| class.with_ranks(class.high, rank))
+                .collect()
+        });
+    }
+
+    if let Some((from, to)) = token.split_once('-') {
+        let from: HandClass = from.parse().map_err(|_| bad_token())?;
+        let to: HandClass = to.parse().map_err(|_| bad_token())?;
+
+        if from.is_pair() && to.is_pair() {
+            return Ok(ranks_between(from.low.min(to.low), from.low.max(to.low))
+                .map(|rank| from.with_ranks(rank, rank))
+                .collect());
+        }
+
+        if from.is_pair() || to.is_pair() || from.high != to.high || from.suitedness != to.suitedness {
+            return Err(RangeError::BadSpan(token.to_string()));
+        }
+
+        return Ok(ranks_between(from.low.min(to.low), from.low.max(to.low))
+            .map(|rank| from.with_ranks(from.high, rank))
+            .collect());
+    }
+
+    Ok(vec![token.parse().map_err(|_| bad_token())?])
+}
+
+// Expands a starting-hand range such as "AKs, 99+, A2s-A5s" into its two-card
+// combinations. Pairs make 6 combinations, suited hands 4, offsuit hands 12
+// and hands without a suffix 16. Hands covered by more than one token are
+// only listed once.
+pub fn parse_range(s: &str) -> Result<Vec<[Card; 2]>, RangeError> {
+    let mut combos = Vec::new();
+
+    for token in s.split(|c: char| c.is_whitespace() || c == ',').filter(|token| !token.is_empty()) {
+        for class in parse_token(token)? {
+            combos.extend(class.combos());
+        }
+    }
+
+    Ok(combos.into_iter().unique().collect())
+}
+
+// How many of 'target_combos' an opponent cannot hold because one of their
+// cards is in 'hole' or on 'board'.
+pub fn blockers(hole: &[Card], board: &[Card], target_combos: &[[Card; 2]]) -> usize {
+    target_combos.iter()
+        .filter(|combo| combo.iter().any(|card| hole.contains(card) || board.contains(card)))
+        .count()
+}
+
+// Draws a starting hand from 'weights' with a chance proportional to its
+// weight, e.g. to give an opponent a realistic range. Combinations holding any
+// of the 'blocked' cards are never drawn, and neither are those whose weight
+// is not a positive, finite number. Gives nothing if no combination is left.
+pub fn sample_hand_from_weights(
+    weights: &[([Card; 2], f64)],
+    blocked: &[Card],
+    rng: &mut impl RngCore
+) -> Option<[Card; 2]>
+{
+    let live: Vec<&([Card; 2], f64)> = weights.iter()
+        .filter(|(_, weight)| weight.is_finite() && *weight > 0.0)
+        .filter(|(combo, _)| !combo.iter().any(|card| blocked.contains(card)))
+        .collect();
+
+    let total: f64 = live.iter().map(|(_, weight)| weight).sum();
+
+    // Finite weights can still add up to more than an f64 holds.
+    if live.is_empty() || !total.is_finite() {
+        return None;
+    }
+
+    let mut target = rng.gen_range(0.0..total);
+
+    for (combo, weight) in &live {
+        if target < *weight {
+            return Some(*combo);
+        }
+
+        target -= weight;
+    }
+
+    // Rounding can leave a sliver past the last combination.
+    live.last().map(|(combo, _)| *combo)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use CardRank::*;
+
+    fn class_ranks(combos: &[[Card; 2]]) -> Vec<(CardRank, CardRank)> {
+        combos.iter()
+            .map(|[a, b]| (a.rank, b.rank))
+            .dedup()
+            .collect()
+    }
+
+    #[test]
+    fn combo_counts() {
+        let suited = parse_range("AKs").unwrap();
+
+        assert_eq!(suited.len(), 4);
+        assert!(suited.iter().all(|[a, b]| a.suit == b.suit));
+        assert!(suited.iter().all(|[a, b]| a.rank == Ace && b.rank == King));
+
+        let offsuit = parse_range("AKo").unwrap();
+
+        assert_eq!(offsuit.len(), 12);
+        assert!(offsuit.iter().all(|[a, b]| a.suit != b.suit));
+
+        assert_eq!(parse_range("AK").unwrap().len(), 16);
+        assert_eq!(parse_range("AA").unwrap().len(), 6);
+    }
+
+    #[test]
+    fn range_expansion() {
+        let pairs = parse_range("99+").unwrap();
+
+        assert_eq!(pairs.len(), 6 * 6);
+        assert_eq!(
+            class_ranks(&pairs),
+            [(Nine, Nine), (Ten, Ten), (Jack, Jack), (Queen, Queen), (King, King), (Ace, Ace)]
+        );
+
+        assert_eq!(
+            class_ranks(&parse_range("A2s-A5s").unwrap()),
+            [(Ace, Two), (Ace, Three), (Ace, Four), (Ace, Five)]
+        );
+
+        assert_eq!(
+            class_ranks(&parse_range("KTo+").unwrap()),
+            [(King, Ten), (King, Jack), (King, Queen)]
+        );
+
+        assert_eq!(class_ranks(&parse_range("44-22").unwrap()), [(Two, Two), (Three, Three), (Four, Four)]);
+
+        // Overlapping tokens do not repeat combinations.
+        assert_eq!(parse_range("TT+, QQ, AKs, AK").unwrap().len(), 5 * 6 + 16);
+    }
+
+    #[test]
+    fn blocking_the_nut_flush() {
+        let board = crate::card::parse_cards("Ks 8s 3s").unwrap();
+
+        // Every spade combination holding the ace makes the nut flush.
+        let nut_flushes: Vec<[Card; 2]> = parse_range("A2s+").unwrap()
+            .into_iter()
+            .filter(|[a, b]| a.suit == CardSuit::Spades && !board.contains(a) && !board.contains(b))
+            .collect();
+
+        assert_eq!(nut_flushes.len(), 9);
+
+        let ace = |suit| Card { suit, rank: Ace };
+        let ten = Card { suit: CardSuit::Diamonds, rank: Ten };
+
+        assert_eq!(blockers(&[ace(CardSuit::Spades), ten], &board, &nut_flushes), 9);
+        assert_eq!(blockers(&[ace(CardSuit::Hearts), ten], &board, &nut_flushes), 0);
+
+        // The board's own spades rule out combinations too.
+        assert_eq!(blockers(&[], &board, &parse_range("AKs").unwrap()), 1);
+    }
+
+    #[test]
+    fn weighted_sampling() {
+        use rand::{rngs::StdRng, SeedableRng};
+
+        let mut rng = StdRng::seed_from_u64(3);
+
+        let weights: Vec<([Card; 2], f64)> = parse_range("AA").unwrap()
+            .into_iter()
+            .map(|combo| (combo, 1000.0))
+            .chain(parse_range("72o").unwrap().into_iter().map(|combo| (combo, 1.0)))
+            .collect();
+
+        let aces = (0..1000)
+            .filter(|_| sample_hand_from_weights(&weights, &[], &mut rng).unwrap()[0].rank == Ace)
+            .count();
+
+        assert!(aces > 990, "{}", aces);
+
+        // Blocking every ace leaves only the sevens.
+        let all_aces: Vec<Card> = CardSuit::all().into_iter().map(|suit| Card { suit, rank: Ace }).collect();
+
+        for _ in 0..100 {
+            assert_eq!(sample_hand_from_weights(&weights, &all_aces, &mut rng).unwrap()[0].rank, Seven);
+        }
+
+        // Nothing is left once the ranges are blocked entirely.
+        let aces_only: Vec<([Card; 2], f64)> = parse_range("AA").unwrap()
+            .into_iter()
+            .map(|combo| (combo, 1.0))
+            .collect();
+
+        assert_eq!(sample_hand_from_weights(&aces_only, &all_aces[..3], &mut rng), None);
+        assert_eq!(sample_hand_from_weights(&[], &[], &mut rng), None);
+
+        // Weights that are not finite are skipped.
+        let mut broken = aces_only.clone();
+        broken[0].1 = f64::INFINITY;
+        broken[1].1 = f64::NAN;
+
+        for _ in 0..100 {
+            let hand = sample_hand_from_weights(&broken, &[], &mut rng).unwrap();
+            assert!(hand != broken[0].0 && hand != broken[1].0);
+        }
+    }
+
+    #[test]
+    fn malformed_ranges() {
+        assert_eq!(parse_range("AAs"), Err(RangeError::BadToken("AAs".to_string())));
+        assert_eq!(parse_range("AX"), Err(RangeError::BadToken("AX".to_string())));
+        assert_eq!(parse_range("AKs, 9"), Err(RangeError::BadToken("9".to_string())));
+        assert_eq!(parse_range("A2s-K5s"), Err(RangeError::BadSpan("A2s-K5s".to_string())));
+        assert_eq!(parse_range("A2s-A5o"), Err(RangeError::BadSpan("A2s-A5o".to_string())));
+        assert_eq!(parse_range("22-A5s"), Err(RangeError::BadSpan("22-A5s".to_string())));
+        assert_eq!(parse_range(""), Ok(vec![]));
+    }
+}
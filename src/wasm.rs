@@ -0,0 +1,73 @@
+// Entry points for JavaScript. Cards are passed as space-separated codes such
+// as "Ah Kd 7c". Nothing here reads the clock or spawns threads, so the crate
+// builds for wasm32-unknown-unknown.
+
+use rand::{rngs::StdRng, SeedableRng};
+use wasm_bindgen::prelude::*;
+
+use crate::card::{parse_cards, Card};
+use crate::equity::equity_vs_random;
+use crate::hand::form_best_hand;
+
+fn parse_hole(hole_js: &str) -> Result<[Card; 2], String> {
+    let cards = parse_cards(hole_js).map_err(|e| e.to_string())?;
+
+    cards.try_into()
+        .map_err(|cards: Vec<Card>| format!("expected 2 hole cards, got {}", cards.len()))
+}
+
+// Describes the best five-card hand, e.g. "Full House, Kings over Threes".
+#[wasm_bindgen]
+pub fn best_hand(board_js: &str, hole_js: &str) -> Result<String, String> {
+    let board = parse_cards(board_js).map_err(|e| e.to_string())?;
+    let hole = parse_cards(hole_js).map_err(|e| e.to_string())?;
+
+    form_best_hand(&board, &hole)
+        .map(|hand| hand.describe())
+        .ok_or_else(|| "at least five cards are needed to make a hand".to_string())
+}
+
+// Equity against a random hand as a JSON object such as
+// {"win":0.85,"tie":0.01,"loss":0.14}. The same seed gives the same result.
+#[wasm_bindgen]
+pub fn equity_seeded(hole_js: &str, board_js: &str, trials: u32, seed: u32) -> Result<String, String> {
+    let hole = parse_hole(hole_js)?;
+    let board = parse_cards(board_js).map_err(|e| e.to_string())?;
+
+    if board.len() > 5 {
+        return Err(format!("a board cannot have {} cards", board.len()));
+    }
+
+    let mut rng = StdRng::seed_from_u64(seed as u64);
+    let equity = equity_vs_random(hole, &board, trials, &mut rng);
+
+    Ok(format!(r#"{{"win":{},"tie":{},"loss":{}}}"#, equity.win, equity.tie, equity.loss))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn best_hand_description() {
+        assert_eq!(
+            best_hand("Kd Kh 3c 9s 2d", "Ks 3h"),
+            Ok("Full House, Kings over Threes".to_string())
+        );
+
+        assert!(best_hand("Kd Kh", "Ks 3h").is_err());
+        assert!(best_hand("Kd Kh 3c 9s Xd", "Ks 3h").is_err());
+    }
+
+    #[test]
+    fn seeded_equity_json() {
+        let json = equity_seeded("Ah As", "", 1000, 41).unwrap();
+
+        assert_eq!(json, equity_seeded("Ah As", "", 1000, 41).unwrap());
+
+        let equity: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+        assert!(equity["win"].as_f64().unwrap() > 0.8, "{}", json);
+        assert!(equity_seeded("Ah", "", 1000, 41).is_err());
+    }
+}
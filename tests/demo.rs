@@ -0,0 +1,45 @@
+use std::process::Command;
+
+fn run_demo(args: &[&str]) -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_poker"))
+        .args(args)
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn seeded_demo_is_stable() {
+    let output = run_demo(&["4", "48"]);
+
+    assert_eq!(output, run_demo(&["4", "48"]));
+
+    assert!(output.contains("River: [♥ 10] [♥  7] [♠  2] [♣  A] [♥  4]\n"), "{}", output);
+    assert!(output.ends_with("Player 1 wins with Pair of Aces\n"), "{}", output);
+}
+
+#[test]
+fn tied_demo_chops_the_pot() {
+    let output = run_demo(&["5", "32"]);
+
+    assert!(output.contains("Players 1, 3 tie with Two Pair, Aces and Sevens\n"), "{}", output);
+
+    // Five antes of 25 leave an odd chip, which goes to the first winner.
+    assert!(
+        output.ends_with("The pot of 125 is chopped: Player 1 gets 63, Player 3 gets 62 (odd chip to Player 1)\n"),
+        "{}",
+        output
+    );
+}
+
+#[test]
+fn demo_rejects_bad_arguments() {
+    let output = Command::new(env!("CARGO_BIN_EXE_poker"))
+        .args(["1"])
+        .output()
+        .unwrap();
+
+    assert!(!output.status.success());
+}